@@ -2,6 +2,17 @@
 #[path = "src/color.rs"]
 mod color;
 
+#[allow(dead_code)]
+#[path = "src/border.rs"]
+mod border;
+
+#[path = "src/layout.rs"]
+mod layout;
+
+#[allow(dead_code)]
+#[path = "src/transition.rs"]
+mod transition;
+
 #[allow(dead_code)]
 #[path = "src/position.rs"]
 mod position;
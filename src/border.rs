@@ -0,0 +1,43 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum BorderStyle {
+    #[default]
+    None,
+    Single,
+    Rounded,
+    Double,
+    Heavy,
+}
+
+pub struct BorderChars {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+}
+
+impl BorderStyle {
+    pub fn chars(&self) -> Option<BorderChars> {
+        let [top_left, top_right, bottom_left, bottom_right, horizontal, vertical] = match self {
+            Self::None => return None,
+            Self::Single => ['┌', '┐', '└', '┘', '─', '│'],
+            Self::Rounded => ['╭', '╮', '╰', '╯', '─', '│'],
+            Self::Double => ['╔', '╗', '╚', '╝', '═', '║'],
+            Self::Heavy => ['┏', '┓', '┗', '┛', '━', '┃'],
+        };
+
+        Some(BorderChars {
+            top_left,
+            top_right,
+            bottom_left,
+            bottom_right,
+            horizontal,
+            vertical,
+        })
+    }
+}
@@ -3,6 +3,7 @@ use crate::{
     segment::Segment::{self, *},
 };
 
+#[derive(Clone)]
 pub enum Character {
     Num(u32),
     Colon,
@@ -24,12 +25,29 @@ impl Character {
         Full, Sides, Full, Right, Full, // 9
     ];
 
-    pub fn fmt(&self, color: &Color, row: usize) -> String {
+    fn segment(&self, row: usize) -> &Segment {
         match self {
             Self::Num(n) => &Self::NUMBERS[*n as usize * 5 + row],
             Self::Colon => &Self::COLON[row],
             Self::Empty => &Empty,
         }
-        .fmt(color)
+    }
+
+    pub fn cells(&self, row: usize) -> Vec<bool> {
+        self.segment(row).cells()
+    }
+
+    pub fn width(&self) -> u16 {
+        self.segment(0).width()
+    }
+
+    pub fn fmt(
+        &self,
+        paint: &dyn Fn(u16) -> Color,
+        reset: &str,
+        fill: Option<char>,
+        row: usize,
+    ) -> String {
+        self.segment(row).fmt(paint, reset, fill)
     }
 }
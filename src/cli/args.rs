@@ -4,16 +4,43 @@ use clap::{
 };
 use serde::Deserialize;
 
-use crate::{color::Color, position::Position};
+use crate::{
+    border::BorderStyle,
+    color::{Animation, ColorDepth, Gradient, Palette},
+    layout::Layout,
+    position::{Margin, Position},
+    transition::Transition,
+};
 
-#[derive(Parser)]
+#[derive(Clone, Parser)]
 #[clap(version = "v0.2.0, (c) 2024 Oughie", hide_possible_values = true, styles = Self::STYLES)]
 pub struct Args {
     #[clap(subcommand)]
     pub mode: Option<Mode>,
+    #[doc = "Use a built-in theme or one from the themes directory"]
+    #[clap(long)]
+    pub theme: Option<String>,
     #[doc = "Specify the clock color"]
     #[clap(long, short)]
-    pub color: Option<Color>,
+    pub color: Option<Palette>,
+    #[doc = "Set how multiple colors are spread across the digits"]
+    #[clap(long, short)]
+    pub gradient: Option<Gradient>,
+    #[doc = "Animate the clock colors"]
+    #[clap(long, short)]
+    pub animation: Option<Animation>,
+    #[doc = "Set the animation period in milliseconds"]
+    #[clap(long)]
+    pub animation_period: Option<u64>,
+    #[doc = "Animate digits when they change"]
+    #[clap(long)]
+    pub transition: Option<Transition>,
+    #[doc = "Set the transition duration in milliseconds"]
+    #[clap(long)]
+    pub transition_duration: Option<u64>,
+    #[doc = "Set the number of colors supported by the terminal"]
+    #[clap(long)]
+    pub color_depth: Option<ColorDepth>,
     #[doc = "Set the polling interval in milliseconds"]
     #[clap(long, short)]
     pub interval: Option<u64>,
@@ -23,12 +50,39 @@ pub struct Args {
     #[doc = "Use bold text"]
     #[clap(long, short)]
     pub bold: bool,
+    #[doc = "Set the layout of the digits"]
+    #[clap(long, short)]
+    pub layout: Option<Layout>,
+    #[doc = "Draw the digits with a fill character instead of colors"]
+    #[clap(long, short)]
+    pub plain: bool,
+    #[doc = "Set the fill character used by --plain"]
+    #[clap(long)]
+    pub fill: Option<char>,
+    #[doc = "Draw a border around the clock"]
+    #[clap(long)]
+    pub border: Option<BorderStyle>,
+    #[doc = "Set the title shown in the top border"]
+    #[clap(long)]
+    pub title: Option<String>,
+    #[doc = "Draw a drop shadow below the border"]
+    #[clap(long)]
+    pub shadow: bool,
     #[doc = "Set the position along the horizontal axis"]
     #[clap(long, short)]
     pub x_pos: Option<Position>,
     #[doc = "Set the position along the vertical axis"]
     #[clap(long, short)]
     pub y_pos: Option<Position>,
+    #[doc = "Set the margins around the clock, e.g. `1`, `1,2` or `1,2,1,2`"]
+    #[clap(long)]
+    pub margin: Option<Margin>,
+    #[doc = "Shift the clock by a few cells every given number of minutes"]
+    #[clap(long)]
+    pub burn_in: Option<u64>,
+    #[doc = "Bounce the clock around the terminal"]
+    #[clap(long)]
+    pub bounce: bool,
     #[doc = "Set the date format"]
     #[clap(long)]
     pub fmt: Option<String>,
@@ -59,6 +113,8 @@ pub enum Mode {
     Timer(TimerArgs),
     #[doc = "Start a stopwatch"]
     Stopwatch,
+    #[doc = "Manage color themes"]
+    Themes(ThemesArgs),
 }
 
 #[derive(clap::Args, Clone, Deserialize)]
@@ -76,3 +132,15 @@ pub struct TimerArgs {
     #[clap(long, short)]
     pub kill: bool,
 }
+
+#[derive(clap::Args, Clone, Deserialize)]
+pub struct ThemesArgs {
+    #[clap(subcommand)]
+    pub command: ThemesCommand,
+}
+
+#[derive(Clone, Subcommand, Deserialize)]
+pub enum ThemesCommand {
+    #[doc = "List all available themes with a preview"]
+    List,
+}
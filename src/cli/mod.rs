@@ -1,13 +1,52 @@
 pub mod args;
 
-use {super::config::Config, args::Args};
+use crate::{color::ColorDepth, config::Config, error::Error, theme::Theme};
+
+use args::{Args, ThemesArgs, ThemesCommand};
 
 impl Args {
-    pub fn overwrite(self, config: &mut Config) {
+    pub fn overwrite(self, config: &mut Config) -> Result<(), Error> {
+        if let Some(theme) = &self.theme {
+            config.general.theme = Some(theme.clone());
+        }
+
+        config.apply_theme()?;
+
+        self.overwrite_options(config);
+
+        Ok(())
+    }
+
+    /// Overwrites everything but the theme, for configurations whose theme is already applied.
+    pub fn overwrite_options(self, config: &mut Config) {
         if let Some(color) = self.color {
             config.general.color = color;
         }
 
+        if let Some(gradient) = self.gradient {
+            config.general.gradient = gradient;
+        }
+
+        if let Some(animation) = self.animation {
+            config.general.animation = animation;
+        }
+
+        if let Some(animation_period) = self.animation_period {
+            config.general.animation_period = animation_period;
+        }
+
+        if let Some(transition) = self.transition {
+            config.general.transition = transition;
+        }
+
+        if let Some(transition_duration) = self.transition_duration {
+            config.general.transition_duration = transition_duration;
+        }
+
+        if let Some(color_depth) = self.color_depth {
+            config.general.color_depth = color_depth;
+        }
+
         if let Some(interval) = self.interval {
             config.general.interval = interval;
         }
@@ -20,6 +59,30 @@ impl Args {
             config.general.bold = true;
         }
 
+        if let Some(layout) = self.layout {
+            config.general.layout = layout;
+        }
+
+        if self.plain {
+            config.general.plain = true;
+        }
+
+        if let Some(fill) = self.fill {
+            config.general.fill = fill;
+        }
+
+        if let Some(border) = self.border {
+            config.border.style = border;
+        }
+
+        if let Some(title) = self.title {
+            config.border.title = Some(title);
+        }
+
+        if self.shadow {
+            config.border.shadow = true;
+        }
+
         if let Some(x_pos) = self.x_pos {
             config.position.x = x_pos;
         }
@@ -28,6 +91,18 @@ impl Args {
             config.position.y = y_pos;
         }
 
+        if let Some(margin) = self.margin {
+            config.position.margin = margin;
+        }
+
+        if let Some(burn_in) = self.burn_in {
+            config.position.burn_in = burn_in;
+        }
+
+        if self.bounce {
+            config.position.bounce = true;
+        }
+
         if let Some(fmt) = self.fmt {
             config.date.fmt = fmt;
         }
@@ -45,3 +120,19 @@ impl Args {
         }
     }
 }
+
+impl ThemesArgs {
+    pub fn run(self) -> Result<(), Error> {
+        match self.command {
+            ThemesCommand::List => {
+                ColorDepth::Auto.init();
+
+                for (name, theme) in Theme::list()? {
+                    println!("{}", theme.preview(&name));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -3,14 +3,18 @@ use std::{
     time::{Duration, Instant},
 };
 
-use crate::state::State;
+use crate::{
+    keys::{Action, KeysConfig},
+    state::State,
+};
 
 pub struct Counter {
-    pub text: &'static str,
     ty: CounterType,
     start: Instant,
     last_pause: Option<Instant>,
     paused: bool,
+    entry: Option<u32>,
+    laps: Vec<Duration>,
 }
 
 pub enum CounterType {
@@ -21,60 +25,217 @@ pub enum CounterType {
 impl Counter {
     pub const DEFAULT_TIMER_DURATION: u64 = 5 * 60;
     pub const MAX_TIMER_DURATION: u64 = 99 * 3600 + 59 * 60 + 59;
-    const TEXT: &'static str = "P: Toggle Pause, R: Restart";
-    const TEXT_PAUSED: &'static str = "P: Toggle Pause, R: Restart [Paused]";
+    const TEXT_PAUSED: &'static str = " [Paused]";
+    const TEXT_ENTRY: &'static str = "Enter: Start, Backspace: Delete, Esc: Cancel";
+    const MAX_ENTRY: u32 = 99_99_99;
 
     pub fn new(ty: CounterType) -> Self {
         Self {
-            text: Self::TEXT,
             ty,
             start: Instant::now(),
             last_pause: None,
             paused: false,
+            entry: None,
+            laps: Vec::new(),
+        }
+    }
+
+    pub fn text(&self, keys: &KeysConfig) -> String {
+        if self.is_entering() {
+            return Self::TEXT_ENTRY.to_string();
+        }
+
+        let mut text = match self.laps[..] {
+            [] => [
+                (Action::Pause, "Toggle Pause"),
+                (Action::Restart, "Restart"),
+            ]
+            .into_iter()
+            .filter_map(|(action, hint)| Some(format!("{}: {hint}", keys.label(action)?)))
+            .collect::<Vec<_>>()
+            .join(", "),
+            [.., last] => {
+                let previous = match self.laps[..] {
+                    [.., previous, _] => previous,
+                    _ => Duration::ZERO,
+                };
+                let split = (last - previous).as_secs();
+
+                format!(
+                    "Lap {}: {:02}:{:02}:{:02}",
+                    self.laps.len(),
+                    split / 3600,
+                    split / 60 % 60,
+                    split % 60
+                )
+            }
+        };
+
+        if self.paused {
+            text.push_str(Self::TEXT_PAUSED);
         }
+
+        text
     }
 
     pub fn toggle_pause(&mut self) {
-        self.text = if self.paused {
+        if self.paused {
             if let Some(last_pause) = self.last_pause {
                 self.start += last_pause.elapsed();
                 self.last_pause = None;
             }
-            Self::TEXT
         } else {
             self.last_pause = Some(Instant::now());
-            Self::TEXT_PAUSED
-        };
+        }
 
         self.paused = !self.paused;
     }
 
+    pub fn is_stopwatch(&self) -> bool {
+        matches!(self.ty, CounterType::Stopwatch)
+    }
+
+    pub fn is_entering(&self) -> bool {
+        self.entry.is_some()
+    }
+
+    pub fn enter_digit(&mut self, digit: u32) {
+        let entry = self.entry.unwrap_or_default() * 10 + digit;
+
+        if entry <= Self::MAX_ENTRY {
+            self.entry = Some(entry);
+        }
+    }
+
+    pub fn erase_digit(&mut self) {
+        if let Some(entry) = &mut self.entry {
+            *entry /= 10;
+        }
+    }
+
+    pub fn cancel_entry(&mut self) {
+        self.entry = None;
+    }
+
+    pub fn confirm_entry(&mut self) {
+        let entry = self.entry.unwrap_or_default();
+
+        self.cancel_entry();
+
+        if entry == 0 {
+            return;
+        }
+
+        if let CounterType::Timer { duration, .. } = &mut self.ty {
+            let seconds = entry / 10_000 * 3600 + entry / 100 % 100 * 60 + entry % 100;
+
+            *duration = Duration::from_secs(u64::from(seconds).min(Self::MAX_TIMER_DURATION));
+            self.restart();
+        }
+    }
+
+    pub fn lap(&mut self) {
+        if self.is_stopwatch() && !self.paused {
+            self.laps.push(self.elapsed());
+        }
+    }
+
     pub fn restart(&mut self) {
         self.start = Instant::now();
         self.last_pause = None;
+        self.laps.clear();
 
         if self.paused {
             self.toggle_pause();
         }
     }
 
-    pub fn get_time(&self) -> (u32, u32, u32) {
-        let mut elapsed = if self.paused {
+    pub fn adjust(&mut self, seconds: i64) {
+        // Rounded up, so that adding a minute to a finished timer shows a whole minute.
+        let finished = self.elapsed().saturating_sub(Duration::from_secs(1));
+        let finished = Duration::from_secs(finished.as_secs_f64().ceil() as u64);
+
+        let CounterType::Timer { duration, .. } = &mut self.ty else {
+            return;
+        };
+
+        let delta = Duration::from_secs(seconds.unsigned_abs());
+        let max = finished + Duration::from_secs(Self::MAX_TIMER_DURATION);
+
+        *duration = if seconds < 0 {
+            duration.saturating_sub(delta).max(finished)
+        } else {
+            ((*duration).max(finished) + delta).min(max)
+        };
+    }
+
+    pub fn parse_duration(s: &str) -> Result<Duration, String> {
+        let mut total_seconds = 0;
+        let mut digits = String::new();
+
+        for c in s.trim().chars() {
+            if c.is_ascii_digit() {
+                digits.push(c);
+                continue;
+            }
+
+            let unit = match c {
+                'h' => 3600,
+                'm' => 60,
+                's' => 1,
+                _ => return Err(format!("invalid unit `{c}` in duration `{s}`")),
+            };
+
+            let value: u64 = digits
+                .parse()
+                .map_err(|_| format!("expected a number before `{c}` in duration `{s}`"))?;
+
+            total_seconds += value * unit;
+            digits.clear();
+        }
+
+        if !digits.is_empty() || s.trim().is_empty() {
+            return Err(format!(
+                "expected a duration like `90s`, `5m` or `1h30m`, found `{s}`"
+            ));
+        }
+
+        Ok(Duration::from_secs(total_seconds))
+    }
+
+    fn elapsed(&self) -> Duration {
+        if self.paused {
             match self.last_pause {
                 Some(last_pause) => last_pause.duration_since(self.start),
                 _ => Duration::from_secs(0),
             }
         } else {
             self.start.elapsed()
+        }
+    }
+
+    pub fn remaining(&self) -> Option<Duration> {
+        let (CounterType::Timer { duration, .. }, None) = (&self.ty, self.entry) else {
+            return None;
         };
 
-        let mut secs = elapsed.as_secs() as u32;
+        let remaining =
+            duration.saturating_sub(self.elapsed().saturating_sub(Duration::from_secs(1)));
+
+        Some(Duration::from_secs(remaining.as_secs()))
+    }
+
+    pub fn get_time(&self) -> (u32, u32, u32) {
+        if let Some(entry) = self.entry {
+            return (entry / 10_000, entry / 100 % 100, entry % 100);
+        }
+
+        let mut secs = self.elapsed().as_secs() as u32;
 
-        if let CounterType::Timer { duration, kill } = self.ty {
-            elapsed = duration.saturating_sub(elapsed.saturating_sub(Duration::from_secs(1)));
-            secs = elapsed.as_secs() as u32;
+        if let (Some(remaining), CounterType::Timer { kill, .. }) = (self.remaining(), &self.ty) {
+            secs = remaining.as_secs() as u32;
 
-            if secs == 0 && kill {
+            if secs == 0 && *kill {
                 State::exit();
                 process::exit(0);
             }
@@ -87,3 +248,73 @@ impl Counter {
         (hours, minutes, seconds)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timer(duration: u64, elapsed: u64) -> Counter {
+        let mut counter = Counter::new(CounterType::Timer {
+            duration: Duration::from_secs(duration),
+            kill: false,
+        });
+
+        counter.start -= Duration::from_secs(elapsed);
+        counter
+    }
+
+    fn remaining(counter: &Counter) -> u64 {
+        counter
+            .remaining()
+            .map(|remaining| remaining.as_secs())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn adjust_changes_remaining_without_moving_start() {
+        let mut counter = timer(300, 120);
+        let start = counter.start;
+
+        counter.adjust(60);
+        assert_eq!(remaining(&counter), 240);
+
+        counter.adjust(-600);
+        assert_eq!(remaining(&counter), 0);
+
+        counter.adjust(60);
+        assert_eq!(remaining(&counter), 60);
+        assert_eq!(counter.start, start);
+        assert_eq!(counter.last_pause, None);
+    }
+
+    #[test]
+    fn adjust_while_paused_keeps_pause() {
+        let mut counter = timer(300, 120);
+
+        counter.toggle_pause();
+        let (start, last_pause) = (counter.start, counter.last_pause);
+
+        counter.adjust(600);
+        assert_eq!(remaining(&counter), 780);
+        assert_eq!((counter.start, counter.last_pause), (start, last_pause));
+
+        counter.toggle_pause();
+        assert_eq!(remaining(&counter), 780);
+    }
+
+    #[test]
+    fn adjust_is_capped_at_the_maximum_duration() {
+        let mut counter = timer(Counter::MAX_TIMER_DURATION - 30, 0);
+
+        counter.adjust(600);
+        assert_eq!(remaining(&counter), Counter::MAX_TIMER_DURATION);
+    }
+
+    #[test]
+    fn adjust_ignores_stopwatch() {
+        let mut counter = Counter::new(CounterType::Stopwatch);
+
+        counter.adjust(600);
+        assert_eq!(counter.remaining(), None);
+    }
+}
@@ -4,79 +4,436 @@ pub mod time_zone;
 
 use std::{
     io::{BufWriter, StdoutLock, Write},
-    time::Duration,
+    mem,
+    time::{Duration, Instant},
 };
 
+use chrono::NaiveTime;
+use crossterm::{cursor::MoveTo, queue};
+
 use crate::{
-    character::Character, clock::mode::ClockMode, color::Color, config::Config, error::Error,
-    position::Position,
+    character::Character,
+    clock::{
+        counter::{Counter, CounterType},
+        mode::{ClockMode, ModeKind},
+        time_zone::TimeZone,
+    },
+    color::{Animation, Color, Gradient, Palette},
+    config::{BorderConfig, ColorsConfig, Config},
+    error::Error,
+    keys::KeysConfig,
+    layout::Layout,
+    position::{Margin, Position},
+    schedule::ScheduleRule,
+    segment,
+    theme::Theme,
+    transition::Transition,
 };
 
+#[derive(Clone, Copy, Default)]
+pub struct Area {
+    pub x: u16,
+    pub y: u16,
+    pub width: u16,
+    pub height: u16,
+}
+
 #[derive(Default)]
 pub struct Padding {
-    pub top: u16,
-    clock: String,
-    text: String,
+    pub area: Area,
+    top: u16,
+    left: u16,
+    block_width: u16,
+    origin: (u16, u16),
+    bounds: (u16, u16),
+    clock: u16,
+    text: u16,
+    text_len: u16,
+    text_hidden: bool,
 }
 
 pub struct Clock {
     pub mode: ClockMode,
+    parked: Vec<ClockMode>,
     pub padding: Padding,
     pub interval: Duration,
     pub x_pos: Position,
     pub y_pos: Position,
-    pub color: Color,
+    pub margin: Margin,
+    pub burn_in: Duration,
+    pub bounce: bool,
+    pub color: Palette,
+    pub gradient: Gradient,
+    pub animation: Animation,
+    pub animation_period: Duration,
+    pub transition: Transition,
+    pub transition_duration: Duration,
+    pub schedule: Vec<ScheduleRule>,
+    pub thresholds: Vec<(Duration, Theme)>,
+    pub flash: Duration,
+    pub colors: ColorsConfig,
     pub use_12h: bool,
     pub hide_seconds: bool,
+    pub time_zone: TimeZone,
     pub blink: bool,
     pub bold: bool,
+    pub layout: Layout,
+    pub fill: Option<char>,
+    pub border: BorderConfig,
+    pub keys: KeysConfig,
+    pub focused: bool,
+    /// The number of panes side by side and on top of each other, to scale the size hint to the terminal.
+    pub panes: (u16, u16),
+    vertical: bool,
+    start: Instant,
+    overlay: Theme,
+    active_rule: Option<usize>,
+    active_thresholds: usize,
+    offset: (i16, i16),
+    velocity: (i16, i16),
+    last_step: Instant,
+    digits: [Option<u32>; 6],
+    changes: [Option<(u32, Instant)>; 6],
 }
 
 impl Clock {
     const WIDTH: u16 = 51;
     const WIDTH_NO_SECONDS: u16 = 32;
-    const HEIGHT: u16 = 7;
+    const WIDTH_VERTICAL: u16 = 13;
+    const DIGITS_HEIGHT: u16 = 5;
     const SUFFIX_LEN: u16 = 5;
     const AM_SUFFIX: &'static str = " [AM]";
     const PM_SUFFIX: &'static str = " [PM]";
+    const FLASH_INTERVAL: u128 = 500;
+    const SHADOW_CHAR: char = '░';
+    const TRANSITION_INTERVAL: Duration = Duration::from_millis(30);
+    const DIM_AMOUNT: f32 = 0.5;
+    const BOUNCE_INTERVAL: Duration = Duration::from_millis(250);
+    const DRIFT_RANGE: i16 = 2;
 
     pub fn new(config: Config, mode: ClockMode) -> Self {
+        let parked = match mode {
+            ClockMode::Time { .. } => Vec::new(),
+            ClockMode::Counter(_) => vec![ClockMode::Time {
+                time_zone: TimeZone::from_config(&config.date),
+                date_format: config.date.fmt.clone(),
+            }],
+        };
+
         Self {
+            parked,
+            fill: config.general.fill(),
             mode,
             padding: Padding::default(),
             interval: Duration::from_millis(config.general.interval),
             x_pos: config.position.x,
             y_pos: config.position.y,
+            margin: config.position.margin,
+            burn_in: Duration::from_secs(config.position.burn_in.saturating_mul(60)),
+            bounce: config.position.bounce,
             color: config.general.color,
+            colors: config.colors,
+            gradient: config.general.gradient,
+            animation: config.general.animation,
+            animation_period: Duration::from_millis(config.general.animation_period),
+            transition: config.general.transition,
+            transition_duration: Duration::from_millis(config.general.transition_duration),
+            schedule: config.schedule,
+            flash: Duration::from_secs(config.timer.flash),
+            thresholds: config.timer.thresholds(),
             use_12h: config.date.use_12h,
             hide_seconds: config.date.hide_seconds,
+            time_zone: TimeZone::from_config(&config.date),
             blink: config.general.blink,
             bold: config.general.bold,
+            layout: config.general.layout,
+            border: config.border,
+            keys: config.keys,
+            focused: true,
+            panes: (1, 1),
+            vertical: false,
+            start: Instant::now(),
+            overlay: Theme::default(),
+            active_rule: None,
+            active_thresholds: 0,
+            offset: (0, 0),
+            velocity: (2, 1),
+            last_step: Instant::now(),
+            digits: [None; 6],
+            changes: [None; 6],
         }
     }
 
-    pub fn update_padding(&mut self, width: u16, height: u16) -> Result<(), Error> {
-        let clock_width = self.width();
-        let text_len = self.mode.text(clock_width)?.len() as u16
+    pub fn configure(&mut self, config: Config) {
+        self.fill = config.general.fill();
+        self.color = config.general.color;
+        self.interval = Duration::from_millis(config.general.interval);
+        self.blink = config.general.blink;
+        self.bold = config.general.bold;
+        self.colors = config.colors;
+        self.gradient = config.general.gradient;
+        self.flash = Duration::from_secs(config.timer.flash);
+        self.set_styles(config.schedule, config.timer.thresholds());
+        self.animation = config.general.animation;
+        self.animation_period = Duration::from_millis(config.general.animation_period);
+        self.layout = config.general.layout;
+        self.transition = config.general.transition;
+        self.transition_duration = Duration::from_millis(config.general.transition_duration);
+
+        self.border = config.border;
+        self.keys = config.keys;
+        self.x_pos = config.position.x;
+        self.y_pos = config.position.y;
+        self.margin = config.position.margin;
+        self.burn_in = Duration::from_secs(config.position.burn_in.saturating_mul(60));
+        self.bounce = config.position.bounce;
+
+        self.use_12h = config.date.use_12h;
+        self.hide_seconds = config.date.hide_seconds;
+        self.time_zone = TimeZone::from_config(&config.date);
+
+        for mode in std::iter::once(&mut self.mode).chain(&mut self.parked) {
+            if let ClockMode::Time {
+                time_zone,
+                date_format,
+            } = mode
+            {
+                *time_zone = self.time_zone;
+                date_format.clone_from(&config.date.fmt);
+            }
+        }
+    }
+
+    pub fn switch_mode(&mut self, kind: ModeKind) -> bool {
+        if self.mode.kind() == kind {
+            return false;
+        }
+
+        let mode = match self.parked.iter().position(|mode| mode.kind() == kind) {
+            Some(index) => self.parked.swap_remove(index),
+            None => match kind {
+                ModeKind::Clock => ClockMode::default(),
+                ModeKind::Stopwatch => ClockMode::Counter(Counter::new(CounterType::Stopwatch)),
+                ModeKind::Timer => ClockMode::Counter(Counter::new(CounterType::Timer {
+                    duration: Duration::from_secs(Counter::DEFAULT_TIMER_DURATION),
+                    kill: false,
+                })),
+            },
+        };
+
+        self.parked.push(mem::replace(&mut self.mode, mode));
+
+        true
+    }
+
+    pub fn update_padding(&mut self, area: Area) -> Result<(), Error> {
+        let Area { width, height, .. } = area;
+
+        self.padding.area = area;
+        self.padding.text_len = self.mode.text(self.width(), &self.keys)?.len() as u16
             + if self.use_12h { Self::SUFFIX_LEN } else { 0 };
 
-        let half_width = clock_width / 2;
+        self.vertical = match self.layout {
+            Layout::Horizontal => false,
+            Layout::Vertical => true,
+            Layout::Auto => self.fits(true, width, height) && !self.fits(false, width, height),
+        };
+
+        let digits_width = self.digits_width();
+        let (frame_width, frame_height) = self.frame_size();
 
-        let column = self.x_pos.calculate(width, half_width);
-        self.padding.top = self.y_pos.calculate(height, Self::HEIGHT / 2);
+        // The vertical layout is meant for narrow panes, so a footer that is too wide is left out.
+        self.padding.text_hidden =
+            self.vertical && self.padding.text_len.max(digits_width) + frame_width >= width;
 
-        self.padding.clock = " ".repeat(column as usize);
-        self.padding.text = format!(
-            "{}{}",
-            self.padding.clock,
-            " ".repeat(half_width.saturating_sub(text_len / 2) as usize)
+        let block_width = if self.padding.text_hidden {
+            digits_width
+        } else {
+            digits_width.max(self.padding.text_len)
+        };
+
+        let (inset_x, inset_y) = if self.border.style.chars().is_some() {
+            (2, 1)
+        } else {
+            (0, 0)
+        };
+
+        let (outer_width, outer_height) = (block_width + frame_width, self.height() + frame_height);
+
+        self.padding.origin = (
+            self.x_pos
+                .calculate(width, outer_width, self.margin.horizontal()),
+            self.y_pos
+                .calculate(height, outer_height, self.margin.vertical()),
+        );
+        self.padding.bounds = (
+            width.saturating_sub(outer_width + 1),
+            height.saturating_sub(outer_height + 1),
         );
 
+        let (origin_x, origin_y) = self.padding.origin;
+        let (max_x, max_y) = self.padding.bounds;
+
+        let column = area.x + origin_x.saturating_add_signed(self.offset.0).min(max_x) + inset_x;
+
+        self.padding.top =
+            area.y + origin_y.saturating_add_signed(self.offset.1).min(max_y) + inset_y;
+        self.padding.left = column;
+        self.padding.block_width = block_width;
+
+        self.padding.clock = column + (block_width - digits_width) / 2;
+        self.padding.text = column + (block_width - self.padding.text_len.min(block_width)) / 2;
+
+        Ok(())
+    }
+
+    pub fn update_offset(&mut self) -> bool {
+        let offset = if self.bounce {
+            if self.last_step.elapsed() < Self::BOUNCE_INTERVAL {
+                return false;
+            }
+
+            self.last_step = Instant::now();
+
+            let (origin_x, origin_y) = self.padding.origin;
+            let (max_x, max_y) = self.padding.bounds;
+            let (x, velocity_x) = Self::bounce_axis(
+                origin_x.saturating_add_signed(self.offset.0).min(max_x),
+                self.velocity.0,
+                max_x,
+            );
+            let (y, velocity_y) = Self::bounce_axis(
+                origin_y.saturating_add_signed(self.offset.1).min(max_y),
+                self.velocity.1,
+                max_y,
+            );
+
+            self.velocity = (velocity_x, velocity_y);
+
+            (x as i16 - origin_x as i16, y as i16 - origin_y as i16)
+        } else if !self.burn_in.is_zero() {
+            let step = self.start.elapsed().as_millis() / self.burn_in.as_millis();
+
+            Self::drift(step)
+        } else {
+            (0, 0)
+        };
+
+        mem::replace(&mut self.offset, offset) != offset
+    }
+
+    fn bounce_axis(position: u16, velocity: i16, max: u16) -> (u16, i16) {
+        let next = position.saturating_add_signed(velocity).min(max);
+
+        if (next == max && velocity > 0) || (next == 0 && velocity < 0) {
+            return (next, -velocity);
+        }
+
+        (next, velocity)
+    }
+
+    fn drift(step: u128) -> (i16, i16) {
+        let side = Self::DRIFT_RANGE as u128 * 2 + 1;
+        let index = (step + side * side / 2) % (side * side);
+        let (row, column) = (index / side, index % side);
+        let column = if row % 2 == 1 {
+            side - 1 - column
+        } else {
+            column
+        };
+
+        (
+            column as i16 - Self::DRIFT_RANGE,
+            row as i16 - Self::DRIFT_RANGE,
+        )
+    }
+
+    pub fn erase(&self, w: &mut BufWriter<StdoutLock<'_>>) -> Result<(), Error> {
+        let (frame_width, frame_height) = self.frame_size();
+        let framed = frame_height > 0;
+        let left = self.padding.left - if framed { 2 } else { 0 };
+        let top = self.padding.top - u16::from(framed);
+        let blank = " ".repeat((self.padding.block_width + frame_width + 1) as usize);
+
+        write!(w, "{}", self.reset())?;
+
+        for y in top..top + self.height() + frame_height {
+            queue!(w, MoveTo(left, y))?;
+            write!(w, "{blank}")?;
+        }
+
+        Ok(())
+    }
+
+    pub fn clear_area(&self, w: &mut BufWriter<StdoutLock<'_>>) -> Result<(), Error> {
+        let Area {
+            x,
+            y,
+            width,
+            height,
+        } = self.padding.area;
+        let blank = " ".repeat(width as usize);
+
+        write!(w, "{}", self.reset())?;
+
+        for row in y..y + height {
+            queue!(w, MoveTo(x, row))?;
+            write!(w, "{blank}")?;
+        }
+
         Ok(())
     }
 
     pub fn is_too_large(&self, width: u16, height: u16) -> bool {
-        self.width() + 1 >= width || Self::HEIGHT + 1 >= height
+        !self.fits(self.vertical, width, height)
+    }
+
+    fn fits(&self, vertical: bool, width: u16, height: u16) -> bool {
+        let (min_width, min_height) = self.min_size(vertical);
+
+        width >= min_width && height >= min_height
+    }
+
+    fn min_size(&self, vertical: bool) -> (u16, u16) {
+        let block_width = if vertical {
+            Self::WIDTH_VERTICAL
+        } else {
+            self.width().max(self.padding.text_len)
+        };
+        let (frame_width, frame_height) = self.frame_size();
+
+        (
+            block_width + frame_width + 1,
+            self.digits_height(vertical) + 2 + frame_height + 1,
+        )
+    }
+
+    /// Returns the minimum size of the layout that needs the fewest additional cells in the current area.
+    fn required_size(&self) -> (u16, u16) {
+        let Area { width, height, .. } = self.padding.area;
+        let layouts: &[bool] = match self.layout {
+            Layout::Horizontal => &[false],
+            Layout::Vertical => &[true],
+            Layout::Auto => &[false, true],
+        };
+
+        layouts
+            .iter()
+            .map(|&vertical| self.min_size(vertical))
+            .min_by_key(|&(min_width, min_height)| {
+                min_width.saturating_sub(width) + min_height.saturating_sub(height)
+            })
+            .unwrap_or_default()
+    }
+
+    fn frame_size(&self) -> (u16, u16) {
+        if self.border.style.chars().is_none() {
+            return (0, 0);
+        }
+
+        let shadow = u16::from(self.border.shadow);
+
+        (4 + shadow, 2 + shadow)
     }
 
     fn width(&self) -> u16 {
@@ -87,62 +444,562 @@ impl Clock {
         Self::WIDTH
     }
 
-    pub fn fmt(&self, w: &mut BufWriter<StdoutLock<'_>>) -> Result<(), Error> {
-        let mut text = self.mode.text(self.width())?;
+    fn digits_width(&self) -> u16 {
+        if self.vertical {
+            return Self::WIDTH_VERTICAL;
+        }
+
+        self.width()
+    }
+
+    fn groups(&self) -> u16 {
+        if self.hide_seconds {
+            return 2;
+        }
+
+        3
+    }
+
+    fn height(&self) -> u16 {
+        self.digits_height(self.vertical) + 2
+    }
+
+    fn digits_height(&self, vertical: bool) -> u16 {
+        if vertical {
+            self.groups() * (Self::DIGITS_HEIGHT + 1) - 1
+        } else {
+            Self::DIGITS_HEIGHT
+        }
+    }
+
+    fn time(&self) -> (u32, u32, u32, Option<&'static str>) {
         let (mut hour, minute, second) = self.mode.get_time();
 
-        if matches!(self.mode, ClockMode::Time { .. }) && self.use_12h {
-            let suffix = if hour < 12 {
-                Self::AM_SUFFIX
-            } else {
-                Self::PM_SUFFIX
-            };
+        if !matches!(self.mode, ClockMode::Time { .. }) || !self.use_12h {
+            return (hour, minute, second, None);
+        }
 
-            text.push_str(suffix);
+        let suffix = if hour < 12 {
+            Self::AM_SUFFIX
+        } else {
+            Self::PM_SUFFIX
+        };
 
-            if hour > 12 {
-                hour -= 12;
-            } else if hour == 0 {
-                hour = 12;
-            }
+        if hour > 12 {
+            hour -= 12;
+        } else if hour == 0 {
+            hour = 12;
         }
 
-        let color = &self.color;
+        (hour, minute, second, Some(suffix))
+    }
+
+    pub fn fmt(&self, w: &mut BufWriter<StdoutLock<'_>>) -> Result<(), Error> {
+        let mut text = self.mode.text(self.width(), &self.keys)?;
+        let (hour, minute, second, suffix) = self.time();
+
+        if let Some(suffix) = suffix {
+            text.push_str(suffix);
+        }
 
-        for row in 0..5 {
-            let colon_character = if self.blink && (second & 1 == 1) {
+        let reset = &self.reset();
+        let colors = self.effective_colors();
+        let digits = self.animate(colors.digits.as_ref().unwrap_or(self.effective_color()));
+        let colon = colors.colon.as_ref().map(|colon| self.animate_color(colon));
+        let digit_colors = (&digits, colon.as_ref());
+        let flashing = self.is_flashing();
+
+        let groups = [hour, minute, second];
+        let groups = &groups[..self.groups() as usize];
+
+        if self.vertical {
+            for (i, value) in groups.iter().enumerate() {
+                let characters = [
+                    (Character::Num(value / 10), Some(i * 2)),
+                    (Character::Num(value % 10), Some(i * 2 + 1)),
+                ];
+
+                for row in 0..5 {
+                    let y = i as u16 * (Self::DIGITS_HEIGHT + 1) + row as u16;
+
+                    if flashing {
+                        self.fmt_blank_row(w, y)?;
+                    } else {
+                        self.fmt_row(w, &characters, digit_colors, row, y, reset)?;
+                    }
+                }
+            }
+        } else {
+            let colon = if self.overlay.blink.unwrap_or(self.blink) && (second & 1 == 1) {
                 Character::Empty
             } else {
                 Character::Colon
             };
 
-            let colon = colon_character.fmt(color, row);
-            let h0 = Character::Num(hour / 10).fmt(color, row);
-            let h1 = Character::Num(hour % 10).fmt(color, row);
-            let m0 = Character::Num(minute / 10).fmt(color, row);
-            let m1 = Character::Num(minute % 10).fmt(color, row);
+            let mut characters = Vec::new();
 
-            write!(w, "{}{h0}{h1}{colon}{m0}{m1}", self.padding.clock)?;
+            for (i, value) in groups.iter().enumerate() {
+                if i > 0 {
+                    characters.push((colon.clone(), None));
+                }
 
-            if !self.hide_seconds {
-                let s0 = Character::Num(second / 10).fmt(color, row);
-                let s1 = Character::Num(second % 10).fmt(color, row);
+                characters.push((Character::Num(value / 10), Some(i * 2)));
+                characters.push((Character::Num(value % 10), Some(i * 2 + 1)));
+            }
 
-                write!(w, "{colon}{s0}{s1}")?;
+            for row in 0..5 {
+                if flashing {
+                    self.fmt_blank_row(w, row as u16)?;
+                } else {
+                    self.fmt_row(w, &characters, digit_colors, row, row as u16, reset)?;
+                }
             }
+        }
 
-            writeln!(w, "\r")?;
+        let text_color = match self.mode {
+            ClockMode::Counter(_) => colors.hint.as_ref(),
+            ClockMode::Time { .. } => colors.text.as_ref(),
+        };
+
+        let dim = if self.focused || self.fill.is_some() {
+            ""
+        } else {
+            Color::DIM
+        };
+
+        if !self.padding.text_hidden {
+            queue!(
+                w,
+                MoveTo(self.padding.text, self.padding.top + self.height() - 1)
+            )?;
+            write!(
+                w,
+                "{}{dim}{text}{reset}",
+                self.text_style(text_color.unwrap_or(self.effective_color().first()))
+            )?;
         }
 
-        let bold_escape_str = if self.bold { Color::BOLD } else { "" };
+        self.fmt_border(w, reset)?;
+
+        Ok(())
+    }
+
+    fn fmt_border(&self, w: &mut BufWriter<StdoutLock<'_>>, reset: &str) -> Result<(), Error> {
+        let Some(chars) = self.border.style.chars() else {
+            return Ok(());
+        };
+
+        let colors = self.effective_colors();
+        let color = self
+            .border
+            .color
+            .as_ref()
+            .or(colors.hint.as_ref())
+            .unwrap_or(self.effective_color().first());
+
+        let style = self.text_style(color);
+        let inner_width = self.padding.block_width as usize + 2;
+        let left = self.padding.left - 2;
+        let right = self.padding.left + self.padding.block_width + 1;
+        let top = self.padding.top - 1;
+        let bottom = self.padding.top + self.height();
+
+        let title = self
+            .border
+            .title
+            .as_deref()
+            .filter(|title| !title.is_empty() && inner_width > 4)
+            .map(|title| title.chars().take(inner_width - 4).collect::<String>());
+
+        let mut top_line = format!("{style}{}", chars.top_left);
+        let mut remaining = inner_width;
+
+        if let Some(title) = title {
+            let title_style = self.text_style(colors.text.as_ref().unwrap_or(color));
+
+            top_line.push_str(&format!(
+                "{}{reset}{title_style} {title} {reset}{style}",
+                chars.horizontal
+            ));
+            remaining -= title.chars().count() + 3;
+        }
+
+        top_line.extend(std::iter::repeat_n(chars.horizontal, remaining));
+        top_line.push(chars.top_right);
+
+        queue!(w, MoveTo(left, top))?;
+        write!(w, "{top_line}{reset}")?;
 
-        writeln!(
+        for y in top + 1..bottom {
+            queue!(w, MoveTo(left, y))?;
+            write!(w, "{style}{}{reset}", chars.vertical)?;
+            queue!(w, MoveTo(right, y))?;
+            write!(w, "{style}{}{reset}", chars.vertical)?;
+        }
+
+        let horizontal = chars.horizontal.to_string().repeat(inner_width);
+
+        queue!(w, MoveTo(left, bottom))?;
+        write!(
             w,
-            "\n{bold_escape_str}{}{}{text}",
-            self.padding.text,
-            self.color.foreground()
+            "{style}{}{horizontal}{}{reset}",
+            chars.bottom_left, chars.bottom_right
         )?;
 
+        if self.border.shadow {
+            let (shadow_style, shadow_char) = match self.fill {
+                Some(_) => (String::new(), Self::SHADOW_CHAR),
+                None => {
+                    let shade = match &colors.background {
+                        Some(background) => background.lerp(&Color::Black, 0.6),
+                        None => Color::BrightBlack,
+                    };
+
+                    (shade.background(), ' ')
+                }
+            };
+
+            for y in top + 1..=bottom {
+                queue!(w, MoveTo(right + 1, y))?;
+                write!(w, "{shadow_style}{shadow_char}{reset}")?;
+            }
+
+            let shadow = shadow_char.to_string().repeat(inner_width + 2);
+
+            queue!(w, MoveTo(left + 1, bottom + 1))?;
+            write!(w, "{shadow_style}{shadow}{reset}")?;
+        }
+
+        Ok(())
+    }
+
+    fn fmt_row(
+        &self,
+        w: &mut BufWriter<StdoutLock<'_>>,
+        characters: &[(Character, Option<usize>)],
+        (digits, colon): (&Palette, Option<&Color>),
+        row: usize,
+        y: u16,
+        reset: &str,
+    ) -> Result<(), Error> {
+        let mut x = 0;
+        let backdrop = self
+            .effective_colors()
+            .background
+            .unwrap_or_else(Color::backdrop);
+
+        queue!(w, MoveTo(self.padding.clock, self.padding.top + y))?;
+
+        for (character, digit) in characters {
+            let paint = |offset: u16| {
+                let color = match (digit, colon) {
+                    (None, Some(colon)) => colon.clone(),
+                    (None, None) if matches!(self.gradient, Gradient::PerDigit) => {
+                        digits.first().clone()
+                    }
+                    _ => self.paint(digits, x + offset, y, digit.unwrap_or_default()),
+                };
+
+                // Unfocused panes are dimmed by blending their digits into the background.
+                if self.focused {
+                    color
+                } else {
+                    color.lerp(&backdrop, Self::DIM_AMOUNT)
+                }
+            };
+
+            let string = match (character, digit) {
+                (Character::Num(value), Some(digit)) => {
+                    self.transition_state(*digit, *value)
+                        .map(|(old, progress)| {
+                            let fade = self.transition.fade(progress);
+                            let cells = self.transition.cells(
+                                &|row| Character::Num(old).cells(row),
+                                &|row| character.cells(row),
+                                row,
+                                progress,
+                            );
+
+                            segment::fmt_cells(
+                                &cells,
+                                &|offset| paint(offset).lerp(&backdrop, fade),
+                                reset,
+                                self.fill,
+                            )
+                        })
+                }
+                _ => None,
+            };
+
+            match string {
+                Some(string) => write!(w, "{string}")?,
+                None => write!(w, "{}", character.fmt(&paint, reset, self.fill, row))?,
+            }
+            x += character.width();
+        }
+
+        Ok(())
+    }
+
+    pub fn update_transitions(&mut self) {
+        let (hour, minute, second, _) = self.time();
+        let values = [
+            hour / 10,
+            hour % 10,
+            minute / 10,
+            minute % 10,
+            second / 10,
+            second % 10,
+        ];
+        let animated = !matches!(self.transition, Transition::None);
+
+        for (i, value) in values.into_iter().enumerate() {
+            if let Some(previous) = self.digits[i].filter(|&previous| previous != value) {
+                self.changes[i] = animated.then(|| (previous, Instant::now()));
+            }
+
+            self.digits[i] = Some(value);
+
+            if self.changes[i].is_some_and(|(_, start)| start.elapsed() >= self.transition_duration)
+            {
+                self.changes[i] = None;
+            }
+        }
+    }
+
+    pub fn tick(&self) -> Duration {
+        if self.changes.iter().any(Option::is_some) {
+            return self.interval.min(Self::TRANSITION_INTERVAL);
+        }
+
+        if self.bounce {
+            return self.interval.min(Self::BOUNCE_INTERVAL);
+        }
+
+        self.interval
+    }
+
+    fn transition_state(&self, digit: usize, value: u32) -> Option<(u32, f32)> {
+        if matches!(self.transition, Transition::None) {
+            return None;
+        }
+
+        match (self.digits[digit], self.changes[digit]) {
+            (Some(current), _) if current != value => Some((current, 0.0)),
+            (_, Some((old, start))) => {
+                let duration = self.transition_duration.as_secs_f32().max(f32::EPSILON);
+
+                Some((old, (start.elapsed().as_secs_f32() / duration).min(1.0)))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn set_styles(&mut self, schedule: Vec<ScheduleRule>, thresholds: Vec<(Duration, Theme)>) {
+        self.schedule = schedule;
+        self.thresholds = thresholds;
+        self.overlay = Theme::default();
+        self.active_rule = None;
+        self.active_thresholds = 0;
+    }
+
+    pub fn update_style(&mut self) -> bool {
+        let (hour, minute, second) = self.time_zone.get_time();
+
+        let active_rule = NaiveTime::from_hms_opt(hour, minute, second)
+            .and_then(|time| self.schedule.iter().rposition(|rule| rule.contains(time)));
+
+        let active_thresholds = match self.remaining() {
+            Some(remaining) => self
+                .thresholds
+                .iter()
+                .take_while(|(threshold, _)| remaining <= *threshold)
+                .count(),
+            None => 0,
+        };
+
+        if active_rule == self.active_rule && active_thresholds == self.active_thresholds {
+            return false;
+        }
+
+        self.active_rule = active_rule;
+        self.active_thresholds = active_thresholds;
+
+        let overlay = active_rule
+            .map(|index| self.schedule[index].style.clone())
+            .unwrap_or_default();
+
+        self.overlay = self.thresholds[..active_thresholds]
+            .iter()
+            .fold(overlay, |overlay, (_, style)| {
+                overlay.overridden_by(style.clone())
+            });
+
+        true
+    }
+
+    fn remaining(&self) -> Option<Duration> {
+        match &self.mode {
+            ClockMode::Counter(counter) => counter.remaining(),
+            ClockMode::Time { .. } => None,
+        }
+    }
+
+    fn is_flashing(&self) -> bool {
+        self.remaining()
+            .is_some_and(|remaining| remaining < self.flash)
+            && self.start.elapsed().as_millis() / Self::FLASH_INTERVAL % 2 == 1
+    }
+
+    fn effective_color(&self) -> &Palette {
+        self.overlay.color.as_ref().unwrap_or(&self.color)
+    }
+
+    fn effective_colors(&self) -> ColorsConfig {
+        let mut colors = self.overlay.colors.clone();
+        let base = &self.colors;
+
+        colors.background = colors.background.or_else(|| base.background.clone());
+
+        if self.overlay.color.is_none() {
+            colors.digits = colors.digits.or_else(|| base.digits.clone());
+            colors.colon = colors.colon.or_else(|| base.colon.clone());
+            colors.text = colors.text.or_else(|| base.text.clone());
+            colors.hint = colors.hint.or_else(|| base.hint.clone());
+        }
+
+        colors
+    }
+
+    fn phase(&self) -> f32 {
+        let period = self.animation_period.as_millis().max(1);
+
+        (self.start.elapsed().as_millis() % period) as f32 / period as f32
+    }
+
+    fn animate(&self, palette: &Palette) -> Palette {
+        if let Animation::None = self.animation {
+            return palette.clone();
+        }
+
+        let phase = self.phase();
+
+        palette.map(|color| self.animation.apply(color, phase))
+    }
+
+    fn animate_color(&self, color: &Color) -> Color {
+        self.animation.apply(color, self.phase())
+    }
+
+    fn fmt_blank_row(&self, w: &mut BufWriter<StdoutLock<'_>>, y: u16) -> Result<(), Error> {
+        let blank = " ".repeat(self.digits_width() as usize);
+
+        queue!(w, MoveTo(self.padding.clock, self.padding.top + y))?;
+        write!(w, "{blank}")?;
+
+        Ok(())
+    }
+
+    fn paint(&self, palette: &Palette, x: u16, y: u16, digit: usize) -> Color {
+        let progress = |position: u16, len: u16| f32::from(position) / f32::from(len.max(2) - 1);
+
+        match self.gradient {
+            Gradient::Horizontal => palette.at(progress(x, self.digits_width())),
+            Gradient::Vertical => palette.at(progress(y, self.height() - 2)),
+            Gradient::PerDigit => palette.get(digit).clone(),
+        }
+    }
+
+    pub fn fmt_fallback(&self, w: &mut BufWriter<StdoutLock<'_>>) -> Result<(), Error> {
+        let Area { width, height, .. } = self.padding.area;
+        let (hour, minute, second, suffix) = self.time();
+
+        let mut time = format!("{hour:02}:{minute:02}");
+
+        if !self.hide_seconds {
+            time.push_str(&format!(":{second:02}"));
+        }
+
+        if let Some(suffix) = suffix {
+            time.push_str(suffix);
+        }
+
+        let (min_width, min_height) = self.required_size();
+        let (columns, rows) = self.panes;
+        let (min_width, min_height) = (
+            min_width.saturating_mul(columns),
+            min_height.saturating_mul(rows),
+        );
+        let size = format!("{min_width}\u{d7}{min_height}");
+        let hint = format!("resize to at least {size}");
+        // A cut off hint would name the wrong size, so it is shortened or left out instead.
+        let hint = [hint, size]
+            .into_iter()
+            .find(|hint| hint.chars().count() <= width as usize);
+
+        let row = height.saturating_sub(1) / 2;
+
+        let colors = self.effective_colors();
+        let text_color = colors
+            .text
+            .as_ref()
+            .unwrap_or(self.effective_color().first());
+        let hint_color = colors
+            .hint
+            .as_ref()
+            .unwrap_or(self.effective_color().first());
+
+        self.write_centered(w, &self.text_style(text_color), &time, width, row)?;
+
+        if let Some(hint) = hint.filter(|_| row + 1 < height) {
+            self.write_centered(w, &self.text_style(hint_color), &hint, width, row + 1)?;
+        }
+
+        Ok(())
+    }
+
+    fn text_style(&self, color: &Color) -> String {
+        if self.fill.is_some() {
+            return String::new();
+        }
+
+        let bold_escape_str = if self.overlay.bold.unwrap_or(self.bold) {
+            Color::BOLD
+        } else {
+            ""
+        };
+
+        format!("{bold_escape_str}{}", color.foreground())
+    }
+
+    pub fn reset(&self) -> String {
+        match &self.effective_colors().background {
+            _ if self.fill.is_some() => String::new(),
+            Some(background) => format!("{}{}", Color::RESET, background.background()),
+            None => Color::RESET.to_string(),
+        }
+    }
+
+    fn write_centered(
+        &self,
+        w: &mut BufWriter<StdoutLock<'_>>,
+        style: &str,
+        line: &str,
+        width: u16,
+        row: u16,
+    ) -> Result<(), Error> {
+        let line: String = line.chars().take(width as usize).collect();
+        let Area { x, y, .. } = self.padding.area;
+        let column = x + (width - line.chars().count() as u16) / 2;
+        let reset = if self.fill.is_some() {
+            ""
+        } else {
+            Color::RESET
+        };
+
+        queue!(w, MoveTo(column, y + row))?;
+        write!(w, "{style}{line}{reset}")?;
+
         Ok(())
     }
 }
@@ -1,7 +1,18 @@
-use crate::error::Error;
+use serde::Deserialize;
+
+use crate::{error::Error, keys::KeysConfig};
 
 use super::{counter::Counter, time_zone::TimeZone};
 
+#[derive(Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModeKind {
+    #[default]
+    Clock,
+    Timer,
+    Stopwatch,
+}
+
 pub enum ClockMode {
     Counter(Counter),
     Time {
@@ -18,9 +29,17 @@ impl ClockMode {
         }
     }
 
-    pub fn text(&self, max_len: u16) -> Result<String, Error> {
+    pub fn kind(&self) -> ModeKind {
+        match self {
+            Self::Counter(counter) if counter.is_stopwatch() => ModeKind::Stopwatch,
+            Self::Counter(_) => ModeKind::Timer,
+            Self::Time { .. } => ModeKind::Clock,
+        }
+    }
+
+    pub fn text(&self, max_len: u16, keys: &KeysConfig) -> Result<String, Error> {
         match self {
-            Self::Counter(counter) => Ok(counter.text.to_string()),
+            Self::Counter(counter) => Ok(counter.text(keys)),
             Self::Time {
                 time_zone,
                 date_format,
@@ -1,16 +1,23 @@
 use std::fmt::Write;
 
-use crate::error::Error;
+use crate::{config::DateConfig, error::Error};
 use chrono::{Local, Timelike, Utc};
+use chrono_tz::Tz;
 
+#[derive(Clone, Copy)]
 pub enum TimeZone {
     Local,
     Utc,
+    Named(Tz),
 }
 
 impl TimeZone {
-    pub fn from_utc(utc: bool) -> Self {
-        if utc {
+    pub fn from_config(date: &DateConfig) -> Self {
+        if let Some(time_zone) = date.timezone {
+            return Self::Named(time_zone);
+        }
+
+        if date.utc {
             return Self::Utc;
         }
 
@@ -18,29 +25,39 @@ impl TimeZone {
     }
 
     pub fn get_time(&self) -> (u32, u32, u32) {
-        if let Self::Utc = self {
-            let utc = Utc::now();
+        match self {
+            Self::Local => {
+                let local = Local::now();
 
-            return (utc.hour(), utc.minute(), utc.second());
-        }
+                (local.hour(), local.minute(), local.second())
+            }
+            Self::Utc => {
+                let utc = Utc::now();
 
-        let local = Local::now();
+                (utc.hour(), utc.minute(), utc.second())
+            }
+            Self::Named(time_zone) => {
+                let time = Utc::now().with_timezone(time_zone);
 
-        (local.hour(), local.minute(), local.second())
+                (time.hour(), time.minute(), time.second())
+            }
+        }
     }
 
     pub fn text(&self, date_format: &str, max_len: u16) -> Result<String, Error> {
         let mut text = String::new();
 
-        write!(
-            text,
-            "{}",
-            match self {
-                Self::Local => Local::now().format(date_format),
-                Self::Utc => Utc::now().format(date_format),
-            }
-        )
-        .map_err(|err| Error::DateFormatInvalid {
+        let result = match self {
+            Self::Local => write!(text, "{}", Local::now().format(date_format)),
+            Self::Utc => write!(text, "{}", Utc::now().format(date_format)),
+            Self::Named(time_zone) => write!(
+                text,
+                "{}",
+                Utc::now().with_timezone(time_zone).format(date_format)
+            ),
+        };
+
+        result.map_err(|err| Error::DateFormatInvalid {
             fmt: date_format.to_string(),
             err: err.to_string(),
         })?;
@@ -1,6 +1,11 @@
-use std::str::FromStr;
+use std::{
+    env,
+    str::FromStr,
+    sync::atomic::{AtomicBool, AtomicU8, Ordering},
+};
 
-use serde::{de, Deserialize, Deserializer};
+use clap::ValueEnum;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 #[macro_export]
 macro_rules! esc {
@@ -9,8 +14,9 @@ macro_rules! esc {
     };
 }
 
-#[derive(Clone, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub enum Color {
+    Auto,
     Black,
     Red,
     Green,
@@ -28,6 +34,7 @@ pub enum Color {
     BrightMagenta,
     BrightCyan,
     BrightWhite,
+    Indexed(u8),
     Rgb {
         r: u8,
         g: u8,
@@ -35,10 +42,53 @@ pub enum Color {
     },
 }
 
+#[derive(Clone)]
+pub struct Palette(Vec<Color>);
+
+#[derive(Clone, Copy, Default, Deserialize, Serialize, ValueEnum)]
+pub enum ColorDepth {
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+    #[serde(rename = "truecolor")]
+    #[value(name = "truecolor")]
+    TrueColor,
+    #[serde(rename = "256")]
+    #[value(name = "256")]
+    Ansi256,
+    #[serde(rename = "16")]
+    #[value(name = "16")]
+    Ansi16,
+}
+
+static COLOR_DEPTH: AtomicU8 = AtomicU8::new(ColorDepth::TrueColor as u8);
+static LIGHT_BACKGROUND: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy, Default, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Animation {
+    #[default]
+    None,
+    Rainbow,
+    Breathe,
+}
+
+#[derive(Clone, Copy, Default, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Gradient {
+    #[default]
+    Horizontal,
+    Vertical,
+    PerDigit,
+}
+
 impl Color {
     pub const RESET: &'static str = esc!(0);
     pub const BOLD: &'static str = esc!(1);
+    pub const DIM: &'static str = esc!(2);
+    pub const REVERSE: &'static str = esc!(7);
     pub const POSSIBLE_VALUES: &[&'static str] = &[
+        "auto",
         "black",
         "red",
         "green",
@@ -56,10 +106,166 @@ impl Color {
         "bright-cyan",
         "bright-white",
         "'#rrggbb'",
+        "'#rgb'",
+        "'rgb(r, g, b)'",
+        "'hsl(h, s%, l%)'",
+        "'color(0-255)'",
+        "CSS color names",
+    ];
+    const CSS_COLORS: [(&'static str, (u8, u8, u8)); 148] = [
+        ("aliceblue", (240, 248, 255)),
+        ("antiquewhite", (250, 235, 215)),
+        ("aqua", (0, 255, 255)),
+        ("aquamarine", (127, 255, 212)),
+        ("azure", (240, 255, 255)),
+        ("beige", (245, 245, 220)),
+        ("bisque", (255, 228, 196)),
+        ("black", (0, 0, 0)),
+        ("blanchedalmond", (255, 235, 205)),
+        ("blue", (0, 0, 255)),
+        ("blueviolet", (138, 43, 226)),
+        ("brown", (165, 42, 42)),
+        ("burlywood", (222, 184, 135)),
+        ("cadetblue", (95, 158, 160)),
+        ("chartreuse", (127, 255, 0)),
+        ("chocolate", (210, 105, 30)),
+        ("coral", (255, 127, 80)),
+        ("cornflowerblue", (100, 149, 237)),
+        ("cornsilk", (255, 248, 220)),
+        ("crimson", (220, 20, 60)),
+        ("cyan", (0, 255, 255)),
+        ("darkblue", (0, 0, 139)),
+        ("darkcyan", (0, 139, 139)),
+        ("darkgoldenrod", (184, 134, 11)),
+        ("darkgray", (169, 169, 169)),
+        ("darkgreen", (0, 100, 0)),
+        ("darkgrey", (169, 169, 169)),
+        ("darkkhaki", (189, 183, 107)),
+        ("darkmagenta", (139, 0, 139)),
+        ("darkolivegreen", (85, 107, 47)),
+        ("darkorange", (255, 140, 0)),
+        ("darkorchid", (153, 50, 204)),
+        ("darkred", (139, 0, 0)),
+        ("darksalmon", (233, 150, 122)),
+        ("darkseagreen", (143, 188, 143)),
+        ("darkslateblue", (72, 61, 139)),
+        ("darkslategray", (47, 79, 79)),
+        ("darkslategrey", (47, 79, 79)),
+        ("darkturquoise", (0, 206, 209)),
+        ("darkviolet", (148, 0, 211)),
+        ("deeppink", (255, 20, 147)),
+        ("deepskyblue", (0, 191, 255)),
+        ("dimgray", (105, 105, 105)),
+        ("dimgrey", (105, 105, 105)),
+        ("dodgerblue", (30, 144, 255)),
+        ("firebrick", (178, 34, 34)),
+        ("floralwhite", (255, 250, 240)),
+        ("forestgreen", (34, 139, 34)),
+        ("fuchsia", (255, 0, 255)),
+        ("gainsboro", (220, 220, 220)),
+        ("ghostwhite", (248, 248, 255)),
+        ("gold", (255, 215, 0)),
+        ("goldenrod", (218, 165, 32)),
+        ("gray", (128, 128, 128)),
+        ("green", (0, 128, 0)),
+        ("greenyellow", (173, 255, 47)),
+        ("grey", (128, 128, 128)),
+        ("honeydew", (240, 255, 240)),
+        ("hotpink", (255, 105, 180)),
+        ("indianred", (205, 92, 92)),
+        ("indigo", (75, 0, 130)),
+        ("ivory", (255, 255, 240)),
+        ("khaki", (240, 230, 140)),
+        ("lavender", (230, 230, 250)),
+        ("lavenderblush", (255, 240, 245)),
+        ("lawngreen", (124, 252, 0)),
+        ("lemonchiffon", (255, 250, 205)),
+        ("lightblue", (173, 216, 230)),
+        ("lightcoral", (240, 128, 128)),
+        ("lightcyan", (224, 255, 255)),
+        ("lightgoldenrodyellow", (250, 250, 210)),
+        ("lightgray", (211, 211, 211)),
+        ("lightgreen", (144, 238, 144)),
+        ("lightgrey", (211, 211, 211)),
+        ("lightpink", (255, 182, 193)),
+        ("lightsalmon", (255, 160, 122)),
+        ("lightseagreen", (32, 178, 170)),
+        ("lightskyblue", (135, 206, 250)),
+        ("lightslategray", (119, 136, 153)),
+        ("lightslategrey", (119, 136, 153)),
+        ("lightsteelblue", (176, 196, 222)),
+        ("lightyellow", (255, 255, 224)),
+        ("lime", (0, 255, 0)),
+        ("limegreen", (50, 205, 50)),
+        ("linen", (250, 240, 230)),
+        ("magenta", (255, 0, 255)),
+        ("maroon", (128, 0, 0)),
+        ("mediumaquamarine", (102, 205, 170)),
+        ("mediumblue", (0, 0, 205)),
+        ("mediumorchid", (186, 85, 211)),
+        ("mediumpurple", (147, 112, 219)),
+        ("mediumseagreen", (60, 179, 113)),
+        ("mediumslateblue", (123, 104, 238)),
+        ("mediumspringgreen", (0, 250, 154)),
+        ("mediumturquoise", (72, 209, 204)),
+        ("mediumvioletred", (199, 21, 133)),
+        ("midnightblue", (25, 25, 112)),
+        ("mintcream", (245, 255, 250)),
+        ("mistyrose", (255, 228, 225)),
+        ("moccasin", (255, 228, 181)),
+        ("navajowhite", (255, 222, 173)),
+        ("navy", (0, 0, 128)),
+        ("oldlace", (253, 245, 230)),
+        ("olive", (128, 128, 0)),
+        ("olivedrab", (107, 142, 35)),
+        ("orange", (255, 165, 0)),
+        ("orangered", (255, 69, 0)),
+        ("orchid", (218, 112, 214)),
+        ("palegoldenrod", (238, 232, 170)),
+        ("palegreen", (152, 251, 152)),
+        ("paleturquoise", (175, 238, 238)),
+        ("palevioletred", (219, 112, 147)),
+        ("papayawhip", (255, 239, 213)),
+        ("peachpuff", (255, 218, 185)),
+        ("peru", (205, 133, 63)),
+        ("pink", (255, 192, 203)),
+        ("plum", (221, 160, 221)),
+        ("powderblue", (176, 224, 230)),
+        ("purple", (128, 0, 128)),
+        ("rebeccapurple", (102, 51, 153)),
+        ("red", (255, 0, 0)),
+        ("rosybrown", (188, 143, 143)),
+        ("royalblue", (65, 105, 225)),
+        ("saddlebrown", (139, 69, 19)),
+        ("salmon", (250, 128, 114)),
+        ("sandybrown", (244, 164, 96)),
+        ("seagreen", (46, 139, 87)),
+        ("seashell", (255, 245, 238)),
+        ("sienna", (160, 82, 45)),
+        ("silver", (192, 192, 192)),
+        ("skyblue", (135, 206, 235)),
+        ("slateblue", (106, 90, 205)),
+        ("slategray", (112, 128, 144)),
+        ("slategrey", (112, 128, 144)),
+        ("snow", (255, 250, 250)),
+        ("springgreen", (0, 255, 127)),
+        ("steelblue", (70, 130, 180)),
+        ("tan", (210, 180, 140)),
+        ("teal", (0, 128, 128)),
+        ("thistle", (216, 191, 216)),
+        ("tomato", (255, 99, 71)),
+        ("turquoise", (64, 224, 208)),
+        ("violet", (238, 130, 238)),
+        ("wheat", (245, 222, 179)),
+        ("white", (255, 255, 255)),
+        ("whitesmoke", (245, 245, 245)),
+        ("yellow", (255, 255, 0)),
+        ("yellowgreen", (154, 205, 50)),
     ];
 
     pub fn foreground(&self) -> String {
-        match self {
+        match self.downsample() {
+            Self::Auto => return Self::auto().foreground(),
             Self::Black => esc!(30),
             Self::Red => esc!(31),
             Self::Green => esc!(32),
@@ -76,13 +282,15 @@ impl Color {
             Self::BrightMagenta => esc!(95),
             Self::BrightCyan => esc!(96),
             Self::BrightWhite => esc!(97),
+            Self::Indexed(n) => return format!("\x1B[38;5;{n}m"),
             Self::Rgb { r, g, b } => return format!("\x1B[38;2;{r};{g};{b}m"),
         }
         .to_string()
     }
 
     pub fn background(&self) -> String {
-        match self {
+        match self.downsample() {
+            Self::Auto => return Self::auto().background(),
             Self::Black => esc!(40),
             Self::Red => esc!(41),
             Self::Green => esc!(42),
@@ -99,21 +307,353 @@ impl Color {
             Self::BrightMagenta => esc!(105),
             Self::BrightCyan => esc!(106),
             Self::BrightWhite => esc!(107),
+            Self::Indexed(n) => return format!("\x1B[48;5;{n}m"),
             Self::Rgb { r, g, b } => return format!("\x1B[48;2;{r};{g};{b}m"),
         }
         .to_string()
     }
 
+    const BASIC: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        match self {
+            Self::Auto => Self::auto().rgb(),
+            Self::Rgb { r, g, b } => (*r, *g, *b),
+            Self::Indexed(n) => Self::indexed_rgb(*n),
+            _ => Self::BASIC[self.basic_index() as usize],
+        }
+    }
+
+    fn basic_index(&self) -> u8 {
+        match self {
+            Self::Black => 0,
+            Self::Red => 1,
+            Self::Green => 2,
+            Self::Yellow => 3,
+            Self::Blue => 4,
+            Self::Magenta => 5,
+            Self::Cyan => 6,
+            Self::White => 7,
+            Self::BrightBlack => 8,
+            Self::BrightRed => 9,
+            Self::BrightGreen => 10,
+            Self::BrightYellow => 11,
+            Self::BrightBlue => 12,
+            Self::BrightMagenta => 13,
+            Self::BrightCyan => 14,
+            Self::BrightWhite => 15,
+            Self::Indexed(n) if *n < 16 => *n,
+            _ => Self::nearest(self.rgb(), Self::BASIC.into_iter().enumerate()),
+        }
+    }
+
+    fn from_basic_index(index: u8) -> Self {
+        match index {
+            0 => Self::Black,
+            1 => Self::Red,
+            2 => Self::Green,
+            3 => Self::Yellow,
+            4 => Self::Blue,
+            5 => Self::Magenta,
+            6 => Self::Cyan,
+            7 => Self::White,
+            8 => Self::BrightBlack,
+            9 => Self::BrightRed,
+            10 => Self::BrightGreen,
+            11 => Self::BrightYellow,
+            12 => Self::BrightBlue,
+            13 => Self::BrightMagenta,
+            14 => Self::BrightCyan,
+            _ => Self::BrightWhite,
+        }
+    }
+
+    fn indexed_rgb(n: u8) -> (u8, u8, u8) {
+        match n {
+            0..=15 => Self::BASIC[n as usize],
+            16..=231 => {
+                let n = n - 16;
+                let level = |i: u8| Self::CUBE_LEVELS[i as usize];
+
+                (level(n / 36), level(n / 6 % 6), level(n % 6))
+            }
+            _ => {
+                let gray = 8 + (n - 232) * 10;
+
+                (gray, gray, gray)
+            }
+        }
+    }
+
+    fn nearest(
+        (r, g, b): (u8, u8, u8),
+        candidates: impl Iterator<Item = (usize, (u8, u8, u8))>,
+    ) -> u8 {
+        let distance = |(cr, cg, cb): (u8, u8, u8)| {
+            let dr = i32::from(r) - i32::from(cr);
+            let dg = i32::from(g) - i32::from(cg);
+            let db = i32::from(b) - i32::from(cb);
+
+            dr * dr + dg * dg + db * db
+        };
+
+        candidates
+            .min_by_key(|(_, rgb)| distance(*rgb))
+            .map_or(0, |(index, _)| index as u8)
+    }
+
+    pub fn to_indexed(&self) -> Self {
+        match self {
+            Self::Rgb { .. } => Self::Indexed(Self::nearest(
+                self.rgb(),
+                (16..=255).map(|n| (n, Self::indexed_rgb(n as u8))),
+            )),
+            _ => self.clone(),
+        }
+    }
+
+    pub fn to_basic(&self) -> Self {
+        match self {
+            Self::Auto => Self::auto(),
+            Self::Rgb { .. } | Self::Indexed(_) => Self::from_basic_index(self.basic_index()),
+            _ => self.clone(),
+        }
+    }
+
+    pub fn lerp(&self, other: &Self, t: f32) -> Self {
+        let (r0, g0, b0) = self.rgb();
+        let (r1, g1, b1) = other.rgb();
+        let mix = |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * t).round() as u8;
+
+        Self::Rgb {
+            r: mix(r0, r1),
+            g: mix(g0, g1),
+            b: mix(b0, b1),
+        }
+    }
+
+    pub fn to_hsl(&self) -> (f32, f32, f32) {
+        let (r, g, b) = self.rgb();
+        let (r, g, b) = (
+            f32::from(r) / 255.0,
+            f32::from(g) / 255.0,
+            f32::from(b) / 255.0,
+        );
+
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+
+        if delta == 0.0 {
+            return (0.0, 0.0, lightness);
+        }
+
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+
+        (hue, saturation, lightness)
+    }
+
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        let saturation = saturation.clamp(0.0, 1.0);
+        let lightness = lightness.clamp(0.0, 1.0);
+
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let hue = hue.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (hue.rem_euclid(2.0) - 1.0).abs());
+
+        let (r, g, b) = match hue as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        let m = lightness - chroma / 2.0;
+        let channel = |value: f32| ((value + m) * 255.0).round() as u8;
+
+        Self::Rgb {
+            r: channel(r),
+            g: channel(g),
+            b: channel(b),
+        }
+    }
+
+    pub fn set_light_background(light: bool) {
+        LIGHT_BACKGROUND.store(light, Ordering::Relaxed);
+    }
+
+    pub fn backdrop() -> Self {
+        if LIGHT_BACKGROUND.load(Ordering::Relaxed) {
+            Self::BrightWhite
+        } else {
+            Self::Black
+        }
+    }
+
+    fn auto() -> Self {
+        if LIGHT_BACKGROUND.load(Ordering::Relaxed) {
+            Self::Black
+        } else {
+            Self::BrightWhite
+        }
+    }
+
+    pub fn downsample(&self) -> Self {
+        match ColorDepth::current() {
+            ColorDepth::Auto | ColorDepth::TrueColor => self.clone(),
+            ColorDepth::Ansi256 => self.to_indexed(),
+            ColorDepth::Ansi16 => self.to_basic(),
+        }
+    }
+
+    pub fn is_disabled() -> bool {
+        env::var_os("NO_COLOR").is_some_and(|no_color| !no_color.is_empty())
+            || env::var_os("TERM").is_some_and(|term| term == "dumb")
+    }
+
     fn parse_rgb_component(hex: &str) -> Result<u8, String> {
         u8::from_str_radix(hex, 16).map_err(|err| err.to_string())
     }
+
+    fn parse_hex(s: &str) -> Result<Self, String> {
+        let hex = &s[1..];
+
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("expected format `#rrggbb` or `#rgb`, found `{s}`"));
+        }
+
+        let (r, g, b) = match hex.len() {
+            3 => (
+                Self::parse_rgb_component(&hex[0..1])? * 17,
+                Self::parse_rgb_component(&hex[1..2])? * 17,
+                Self::parse_rgb_component(&hex[2..3])? * 17,
+            ),
+            6 => (
+                Self::parse_rgb_component(&hex[0..2])?,
+                Self::parse_rgb_component(&hex[2..4])?,
+                Self::parse_rgb_component(&hex[4..6])?,
+            ),
+            _ => return Err(format!("expected format `#rrggbb` or `#rgb`, found `{s}`")),
+        };
+
+        Ok(Self::Rgb { r, g, b })
+    }
+
+    fn parse_function(s: &str) -> Option<Result<Self, String>> {
+        let (name, arguments) = s.strip_suffix(')')?.split_once('(')?;
+        let arguments: Vec<&str> = arguments
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|argument| !argument.is_empty())
+            .collect();
+
+        let parse = |argument: &str, suffix: &str, max: f32| {
+            argument
+                .strip_suffix(suffix)
+                .unwrap_or(argument)
+                .parse::<f32>()
+                .ok()
+                .filter(|value| (0.0..=max).contains(value))
+                .ok_or_else(|| format!("invalid argument `{argument}` in `{s}`"))
+        };
+
+        let color = match (
+            name.trim().to_ascii_lowercase().as_str(),
+            arguments.as_slice(),
+        ) {
+            ("rgb", [r, g, b]) => parse(r, "", 255.0).and_then(|r| {
+                Ok(Self::Rgb {
+                    r: r as u8,
+                    g: parse(g, "", 255.0)? as u8,
+                    b: parse(b, "", 255.0)? as u8,
+                })
+            }),
+            ("hsl", [h, s, l]) => parse(h, "deg", 360.0).and_then(|h| {
+                Ok(Self::from_hsl(
+                    h,
+                    parse(s, "%", 100.0)? / 100.0,
+                    parse(l, "%", 100.0)? / 100.0,
+                ))
+            }),
+            ("color", [n]) => parse(n, "", 255.0).map(|n| Self::Indexed(n as u8)),
+            ("rgb" | "hsl", _) => Err(format!("expected three arguments, found `{s}`")),
+            ("color", _) => Err(format!("expected a single argument, found `{s}`")),
+            _ => return None,
+        };
+
+        Some(color)
+    }
+
+    pub fn names() -> impl Iterator<Item = &'static str> {
+        Self::POSSIBLE_VALUES[..17]
+            .iter()
+            .copied()
+            .chain(Self::CSS_COLORS.iter().map(|(name, _)| *name))
+    }
+
+    fn closest_name(s: &str) -> Option<&'static str> {
+        Self::names()
+            .map(|name| (name, Self::edit_distance(s, name)))
+            .filter(|(_, distance)| *distance <= (s.len() / 3).max(2))
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(name, _)| name)
+    }
+
+    fn edit_distance(a: &str, b: &str) -> usize {
+        let b: Vec<char> = b.chars().collect();
+        let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+        for (i, a) in a.chars().enumerate() {
+            let mut current = vec![i + 1];
+
+            for (j, b) in b.iter().enumerate() {
+                let substitution = previous[j] + usize::from(a != *b);
+
+                current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+            }
+
+            previous = current;
+        }
+
+        previous[b.len()]
+    }
 }
 
 impl FromStr for Color {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
+        let lowercase = s.to_ascii_lowercase();
+
+        Ok(match lowercase.as_str() {
+            "auto" => Self::Auto,
             "black" => Self::Black,
             "red" => Self::Red,
             "green" => Self::Green,
@@ -130,22 +670,30 @@ impl FromStr for Color {
             "bright-magenta" => Self::BrightMagenta,
             "bright-cyan" => Self::BrightCyan,
             "bright-white" => Self::BrightWhite,
-            _ if s.starts_with('#') => {
-                if s.len() != 7 {
-                    return Err(format!("expected format `#rrggbb`, found `{s}`"));
+            _ if s.starts_with('#') => Self::parse_hex(s)?,
+            _ => {
+                if let Some(color) = Self::parse_function(s) {
+                    return color;
                 }
 
-                let r = Self::parse_rgb_component(&s[1..3])?;
-                let g = Self::parse_rgb_component(&s[3..5])?;
-                let b = Self::parse_rgb_component(&s[5..7])?;
+                if let Some((_, (r, g, b))) =
+                    Self::CSS_COLORS.iter().find(|(name, _)| *name == lowercase)
+                {
+                    return Ok(Self::Rgb {
+                        r: *r,
+                        g: *g,
+                        b: *b,
+                    });
+                }
 
-                Self::Rgb { r, g, b }
-            }
-            _ => {
                 let green_fg = Color::Green.foreground();
+                let suggestion = match Self::closest_name(&lowercase) {
+                    Some(name) => format!("\n  did you mean `{green_fg}{name}{}`?", Color::RESET),
+                    None => String::new(),
+                };
 
                 return Err(format!(
-                    "color `{s}` is neither a recognized color nor a valid hex color code.\n  [possible values: {}{}{}]",
+                    "color `{s}` is neither a recognized color nor a valid color code.{suggestion}\n  [possible values: {}{}{}]",
                     green_fg,
                     Self::POSSIBLE_VALUES.join(&format!("{},{} ", Color::RESET, green_fg)),
                     Color::RESET
@@ -165,3 +713,244 @@ impl<'de> Deserialize<'de> for Color {
         Color::from_str(&string).map_err(de::Error::custom)
     }
 }
+
+impl Serialize for Color {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Auto => serializer.serialize_str(Self::POSSIBLE_VALUES[0]),
+            Self::Indexed(n) => serializer.collect_str(&format_args!("color({n})")),
+            Self::Rgb { r, g, b } => {
+                serializer.collect_str(&format_args!("#{r:02x}{g:02x}{b:02x}"))
+            }
+            _ => serializer.serialize_str(Self::POSSIBLE_VALUES[self.basic_index() as usize + 1]),
+        }
+    }
+}
+
+impl ColorDepth {
+    pub fn current() -> Self {
+        match COLOR_DEPTH.load(Ordering::Relaxed) {
+            2 => Self::Ansi256,
+            3 => Self::Ansi16,
+            _ => Self::TrueColor,
+        }
+    }
+
+    pub fn set(self) {
+        COLOR_DEPTH.store(self as u8, Ordering::Relaxed);
+    }
+}
+
+impl Animation {
+    pub fn apply(&self, color: &Color, phase: f32) -> Color {
+        let (hue, saturation, lightness) = color.to_hsl();
+
+        match self {
+            Self::None => color.clone(),
+            Self::Rainbow => {
+                let (saturation, lightness) = if saturation < 0.1 {
+                    (1.0, 0.5)
+                } else {
+                    (saturation, lightness)
+                };
+
+                Color::from_hsl(hue + phase * 360.0, saturation, lightness)
+            }
+            Self::Breathe => {
+                let brightness = 1.0 - 0.35 * (1.0 - (phase * std::f32::consts::TAU).cos());
+
+                Color::from_hsl(hue, saturation, lightness * brightness)
+            }
+        }
+    }
+}
+
+impl Palette {
+    pub fn first(&self) -> &Color {
+        &self.0[0]
+    }
+
+    pub fn get(&self, index: usize) -> &Color {
+        &self.0[index % self.0.len()]
+    }
+
+    pub fn at(&self, t: f32) -> Color {
+        let [color] = self.0.as_slice() else {
+            let last = (self.0.len() - 1) as f32;
+            let position = t.clamp(0.0, 1.0) * last;
+            let index = (position.floor() as usize).min(self.0.len() - 2);
+
+            return self.0[index].lerp(&self.0[index + 1], position - index as f32);
+        };
+
+        color.clone()
+    }
+
+    pub fn contains(&self, color: &Color) -> bool {
+        self.0.contains(color)
+    }
+
+    pub fn map(&self, f: impl Fn(&Color) -> Color) -> Self {
+        Self(self.0.iter().map(f).collect())
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::from(Color::default())
+    }
+}
+
+impl From<Color> for Palette {
+    fn from(color: Color) -> Self {
+        Self(vec![color])
+    }
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut colors = Vec::new();
+        let mut depth = 0;
+        let mut start = 0;
+
+        for (i, c) in s.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => {
+                    colors.push(Color::from_str(s[start..i].trim())?);
+                    start = i + 1;
+                }
+                _ => (),
+            }
+        }
+
+        colors.push(Color::from_str(s[start..].trim())?);
+
+        Ok(Self(colors))
+    }
+}
+
+impl<'de> Deserialize<'de> for Palette {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // The colors are parsed by hand, as an untagged enum would hide their errors.
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum PaletteRepr {
+            Single(String),
+            List(Vec<String>),
+        }
+
+        match PaletteRepr::deserialize(deserializer)? {
+            PaletteRepr::Single(palette) => Self::from_str(&palette).map_err(de::Error::custom),
+            PaletteRepr::List(colors) if colors.is_empty() => {
+                Err(de::Error::custom("expected at least one color"))
+            }
+            PaletteRepr::List(colors) => colors
+                .iter()
+                .map(|color| Color::from_str(color))
+                .collect::<Result<_, _>>()
+                .map(Self)
+                .map_err(de::Error::custom),
+        }
+    }
+}
+
+impl Serialize for Palette {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0.as_slice() {
+            [color] => color.serialize(serializer),
+            colors => colors.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn color(s: &str) -> Color {
+        s.parse().unwrap()
+    }
+
+    fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color::Rgb { r, g, b }
+    }
+
+    fn is_invalid(s: &str) -> bool {
+        s.parse::<Color>().is_err()
+    }
+
+    #[test]
+    fn parses_names() {
+        assert_eq!(color("auto"), Color::Auto);
+        assert_eq!(color("red"), Color::Red);
+        assert_eq!(color("Bright-Blue"), Color::BrightBlue);
+        assert_eq!(color("orange"), rgb(255, 165, 0));
+        assert_eq!(color("Purple"), rgb(128, 0, 128));
+    }
+
+    #[test]
+    fn parses_hex_codes() {
+        assert_eq!(color("#ff8000"), rgb(255, 128, 0));
+        assert_eq!(color("#F80"), rgb(255, 136, 0));
+
+        for s in ["#", "#ff80", "#ff80000", "#gg0000", "#+10000", "#\u{fc}00"] {
+            assert!(is_invalid(s), "`{s}` should be rejected");
+        }
+    }
+
+    #[test]
+    fn parses_functions() {
+        assert_eq!(color("rgb(255, 128, 0)"), rgb(255, 128, 0));
+        assert_eq!(color("RGB(1 2 3)"), rgb(1, 2, 3));
+        assert_eq!(color("hsl(0, 100%, 50%)"), rgb(255, 0, 0));
+        assert_eq!(color("hsl(240deg 100% 50%)"), rgb(0, 0, 255));
+        assert_eq!(color("color(42)"), Color::Indexed(42));
+    }
+
+    #[test]
+    fn rejects_out_of_range_arguments() {
+        for s in [
+            "rgb(256, 0, 0)",
+            "rgb(-1, 0, 0)",
+            "rgb(1, 2)",
+            "hsl(361, 0%, 0%)",
+            "hsl(0, 101%, 50%)",
+            "color(256)",
+            "color(1, 2)",
+        ] {
+            assert!(is_invalid(s), "`{s}` should be rejected");
+        }
+    }
+
+    #[test]
+    fn suggests_close_names() {
+        let err = "redd".parse::<Color>().unwrap_err();
+
+        assert!(err.contains("did you mean"), "{err}");
+        assert!(!"xyzzyq"
+            .parse::<Color>()
+            .unwrap_err()
+            .contains("did you mean"));
+
+        assert_eq!(Color::closest_name("blu"), Some("blue"));
+        assert_eq!(Color::closest_name("bright-gren"), Some("bright-green"));
+        assert_eq!(
+            Color::closest_name("lightgoldenrodyelow"),
+            Some("lightgoldenrodyellow")
+        );
+        assert_eq!(Color::closest_name("bluuuue"), None);
+    }
+}
@@ -0,0 +1,407 @@
+use std::{str::FromStr, time::Duration};
+
+use chrono_tz::TZ_VARIANTS;
+use clap::ValueEnum;
+
+use crate::{
+    border::BorderStyle,
+    clock::{
+        counter::{Counter, CounterType},
+        mode::{ClockMode, ModeKind},
+        time_zone::TimeZone,
+        Clock,
+    },
+    color::{Animation, Color, ColorDepth, Gradient, Palette},
+    config::Config,
+    error::Error,
+    layout::Layout,
+    position::{Margin, Position},
+    theme::Theme,
+    transition::Transition,
+};
+
+#[derive(Clone, Copy)]
+pub enum Toggle {
+    Bold,
+    Blink,
+    Plain,
+    Shadow,
+    Bounce,
+    Use12h,
+    Seconds,
+}
+
+pub enum Command {
+    Mode(ModeKind, Option<Duration>),
+    Theme(String, Theme),
+    Color(Palette),
+    Gradient(Gradient),
+    Animation(Animation),
+    AnimationPeriod(u64),
+    Transition(Transition),
+    TransitionDuration(u64),
+    ColorDepth(ColorDepth),
+    Interval(u64),
+    Layout(Layout),
+    Fill(char),
+    Border(BorderStyle),
+    Title(Option<String>),
+    Toggle(Toggle, Option<bool>),
+    Position(Position, Option<Position>),
+    Margin(Margin),
+    BurnIn(u64),
+    TimeZone(TimeZone),
+    Fmt(String),
+    Write,
+    Quit,
+}
+
+impl Toggle {
+    const ALL: [Self; 7] = [
+        Self::Bold,
+        Self::Blink,
+        Self::Plain,
+        Self::Shadow,
+        Self::Bounce,
+        Self::Use12h,
+        Self::Seconds,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Bold => "bold",
+            Self::Blink => "blink",
+            Self::Plain => "plain",
+            Self::Shadow => "shadow",
+            Self::Bounce => "bounce",
+            Self::Use12h => "12h",
+            Self::Seconds => "seconds",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|toggle| toggle.name() == name)
+    }
+
+    pub fn apply(self, config: &mut Config, value: Option<bool>) -> bool {
+        let (field, inverted) = match self {
+            Self::Bold => (&mut config.general.bold, false),
+            Self::Blink => (&mut config.general.blink, false),
+            Self::Plain => (&mut config.general.plain, false),
+            Self::Shadow => (&mut config.border.shadow, false),
+            Self::Bounce => (&mut config.position.bounce, false),
+            Self::Use12h => (&mut config.date.use_12h, false),
+            Self::Seconds => (&mut config.date.hide_seconds, true),
+        };
+
+        let value = value.unwrap_or(*field == inverted);
+
+        *field = value != inverted;
+
+        value
+    }
+}
+
+impl Command {
+    const NAMES: [&'static str; 30] = [
+        "12h",
+        "animation",
+        "animation-period",
+        "blink",
+        "bold",
+        "border",
+        "bounce",
+        "burn-in",
+        "clock",
+        "color",
+        "color-depth",
+        "fill",
+        "fmt",
+        "gradient",
+        "interval",
+        "layout",
+        "margin",
+        "plain",
+        "pos",
+        "quit",
+        "seconds",
+        "shadow",
+        "stopwatch",
+        "theme",
+        "timer",
+        "title",
+        "transition",
+        "transition-duration",
+        "tz",
+        "write",
+    ];
+
+    /// Applies the command to the clock and its configuration.
+    /// Returns a summary when the outcome is not evident from the input, such as for toggles.
+    pub fn apply(self, clock: &mut Clock, config: &mut Config) -> Option<String> {
+        match self {
+            Self::Mode(kind, duration) => {
+                clock.switch_mode(kind);
+
+                if let Some(duration) = duration {
+                    clock.mode = ClockMode::Counter(Counter::new(CounterType::Timer {
+                        duration,
+                        kill: false,
+                    }));
+                }
+            }
+            Self::Theme(name, theme) => {
+                config.general.theme = Some(name);
+                config.colors = Default::default();
+                theme.apply(config);
+            }
+            Self::Color(color) => config.general.color = color,
+            Self::Gradient(gradient) => config.general.gradient = gradient,
+            Self::Animation(animation) => config.general.animation = animation,
+            Self::AnimationPeriod(period) => config.general.animation_period = period,
+            Self::Transition(transition) => config.general.transition = transition,
+            Self::TransitionDuration(duration) => config.general.transition_duration = duration,
+            Self::ColorDepth(color_depth) => {
+                config.general.color_depth = color_depth;
+                color_depth.init();
+            }
+            Self::Interval(interval) => config.general.interval = interval,
+            Self::Layout(layout) => config.general.layout = layout,
+            Self::Fill(fill) => config.general.fill = fill,
+            Self::Border(style) => config.border.style = style,
+            Self::Title(title) => config.border.title = title,
+            Self::Toggle(toggle, value) => {
+                let state = if toggle.apply(config, value) {
+                    "on"
+                } else {
+                    "off"
+                };
+
+                return Some(format!("{} {state}", toggle.name()));
+            }
+            Self::Position(x, y) => {
+                config.position.x = x;
+
+                if let Some(y) = y {
+                    config.position.y = y;
+                }
+            }
+            Self::Margin(margin) => config.position.margin = margin,
+            Self::BurnIn(burn_in) => config.position.burn_in = burn_in,
+            Self::TimeZone(time_zone) => {
+                (config.date.utc, config.date.timezone) = match time_zone {
+                    TimeZone::Local => (false, None),
+                    TimeZone::Utc => (true, None),
+                    TimeZone::Named(time_zone) => (false, Some(time_zone)),
+                };
+            }
+            Self::Fmt(fmt) => config.date.fmt = fmt,
+            Self::Write | Self::Quit => (),
+        }
+
+        None
+    }
+
+    /// Returns the byte offset of the word under the cursor and the values it could be completed to.
+    pub fn complete(input: &str) -> (usize, Vec<String>) {
+        let start = input
+            .char_indices()
+            .rev()
+            .find(|(_, character)| character.is_whitespace())
+            .map_or(0, |(index, character)| index + character.len_utf8());
+        let word = input[start..].to_lowercase();
+
+        let candidates = if start == 0 {
+            Self::NAMES.iter().map(ToString::to_string).collect()
+        } else {
+            Self::arguments(input.split_whitespace().next().unwrap_or_default())
+        };
+
+        let candidates = candidates
+            .into_iter()
+            .filter(|candidate| candidate.to_lowercase().starts_with(&word))
+            .collect();
+
+        (start, candidates)
+    }
+
+    fn arguments(name: &str) -> Vec<String> {
+        match name {
+            "color" => Color::names().map(ToString::to_string).collect(),
+            "theme" => Theme::list()
+                .map(|themes| themes.into_iter().map(|(name, _)| name).collect())
+                .unwrap_or_default(),
+            "tz" => ["local", "utc"]
+                .into_iter()
+                .map(ToString::to_string)
+                .chain(
+                    TZ_VARIANTS
+                        .iter()
+                        .map(|time_zone| time_zone.name().to_string()),
+                )
+                .collect(),
+            "gradient" => variants::<Gradient>(),
+            "animation" => variants::<Animation>(),
+            "transition" => variants::<Transition>(),
+            "color-depth" => variants::<ColorDepth>(),
+            "layout" => variants::<Layout>(),
+            "border" => variants::<BorderStyle>(),
+            "pos" => ["start", "center", "end"]
+                .into_iter()
+                .map(ToString::to_string)
+                .collect(),
+            name if Toggle::from_name(name).is_some() => {
+                vec!["on".to_string(), "off".to_string()]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (name, args) = s
+            .split_once(char::is_whitespace)
+            .map(|(name, args)| (name, args.trim()))
+            .unwrap_or((s, ""));
+
+        let required = || {
+            if args.is_empty() {
+                Err(format!("`{name}` expects an argument"))
+            } else {
+                Ok(args)
+            }
+        };
+        let number = || {
+            required()?
+                .parse::<u64>()
+                .map_err(|_| format!("expected a number, found `{args}`"))
+        };
+
+        Ok(match name {
+            "clock" => Self::Mode(ModeKind::Clock, None),
+            "stopwatch" => Self::Mode(ModeKind::Stopwatch, None),
+            "timer" if args.is_empty() => Self::Mode(ModeKind::Timer, None),
+            "timer" => {
+                let duration = Counter::parse_duration(args)?;
+                let total_seconds = duration.as_secs();
+
+                if total_seconds > Counter::MAX_TIMER_DURATION {
+                    return Err(Error::TimerDurationTooLong {
+                        hours: total_seconds / 3600,
+                        minutes: total_seconds / 60 % 60,
+                        seconds: total_seconds % 60,
+                    }
+                    .to_string());
+                }
+
+                Self::Mode(ModeKind::Timer, Some(duration))
+            }
+            "theme" => {
+                let name = required()?;
+                let theme = Theme::load(name).map_err(|err| err.to_string())?;
+
+                Self::Theme(name.to_string(), theme)
+            }
+            "color" => Self::Color(required()?.parse()?),
+            "gradient" => Self::Gradient(choice(required()?)?),
+            "animation" => Self::Animation(choice(required()?)?),
+            "animation-period" => Self::AnimationPeriod(number()?),
+            "transition" => Self::Transition(choice(required()?)?),
+            "transition-duration" => Self::TransitionDuration(number()?),
+            "color-depth" => Self::ColorDepth(choice(required()?)?),
+            "interval" => Self::Interval(number()?),
+            "layout" => Self::Layout(choice(required()?)?),
+            "fill" => {
+                let mut chars = required()?.chars();
+
+                match (chars.next(), chars.next()) {
+                    (Some(fill), None) => Self::Fill(fill),
+                    _ => return Err(format!("expected a single character, found `{args}`")),
+                }
+            }
+            "border" => Self::Border(choice(required()?)?),
+            "title" => Self::Title(Some(args.to_string()).filter(|title| !title.is_empty())),
+            "pos" => {
+                let mut positions = required()?.split_whitespace().map(str::parse);
+
+                match (positions.next(), positions.next(), positions.next()) {
+                    (Some(x), y, None) => Self::Position(x?, y.transpose()?),
+                    _ => return Err(format!("expected one or two positions, found `{args}`")),
+                }
+            }
+            "margin" => Self::Margin(required()?.parse()?),
+            "burn-in" => Self::BurnIn(number()?),
+            "tz" => Self::TimeZone(match required()?.to_lowercase().as_str() {
+                "local" => TimeZone::Local,
+                "utc" => TimeZone::Utc,
+                _ => TimeZone::Named(
+                    TZ_VARIANTS
+                        .into_iter()
+                        .find(|time_zone| time_zone.name().eq_ignore_ascii_case(args))
+                        .ok_or_else(|| format!("unknown time zone `{args}`"))?,
+                ),
+            }),
+            "fmt" => Self::Fmt(required()?.to_string()),
+            "w" | "write" => Self::Write,
+            "q" | "quit" => Self::Quit,
+            name => match Toggle::from_name(name) {
+                Some(toggle) => Self::Toggle(
+                    toggle,
+                    match args.to_lowercase().as_str() {
+                        "" => None,
+                        "on" | "true" => Some(true),
+                        "off" | "false" => Some(false),
+                        _ => return Err(format!("expected `on` or `off`, found `{args}`")),
+                    },
+                ),
+                None if name.is_empty() => return Err("no command given".to_string()),
+                None => return Err(format!("unknown command `{name}`")),
+            },
+        })
+    }
+}
+
+fn variants<T: ValueEnum>() -> Vec<String> {
+    T::value_variants()
+        .iter()
+        .filter_map(ValueEnum::to_possible_value)
+        .map(|value| value.get_name().to_string())
+        .collect()
+}
+
+fn choice<T: ValueEnum>(s: &str) -> Result<T, String> {
+    T::from_str(s, true).map_err(|_| {
+        format!(
+            "invalid value `{s}`, expected one of: {}",
+            variants::<T>().join(", ")
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completes_after_multibyte_whitespace() {
+        let (start, candidates) = Command::complete("color\u{a0}re");
+
+        assert_eq!(start, "color\u{a0}".len());
+        assert!(candidates.contains(&"red".to_string()));
+    }
+
+    #[test]
+    fn completes_command_names() {
+        assert_eq!(
+            Command::complete("tr"),
+            (
+                0,
+                vec!["transition".to_string(), "transition-duration".to_string()]
+            )
+        );
+    }
+}
@@ -1,56 +1,145 @@
 use std::{
+    collections::{BTreeMap, BTreeSet},
     env::{self, VarError},
-    fs,
+    fs, io, mem,
     path::Path,
+    time::Duration,
 };
 
-use serde::Deserialize;
+use chrono_tz::Tz;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use toml_edit::{DocumentMut, Item, TableLike};
 
-use crate::{color::Color, error::Error, position::Position};
+use crate::{
+    border::BorderStyle,
+    color::{Animation, Color, ColorDepth, Gradient, Palette},
+    error::Error,
+    keys::KeysConfig,
+    layout::Layout,
+    pane::{Pane, Split},
+    position::{Margin, Position},
+    schedule::ScheduleRule,
+    theme::Theme,
+    threshold::{Threshold, ThresholdStyle},
+    transition::Transition,
+};
 
-#[derive(Default, Deserialize)]
+#[derive(Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct Config {
     pub general: GeneralConfig,
+    pub colors: ColorsConfig,
     pub position: PositionConfig,
     pub date: DateConfig,
+    pub border: BorderConfig,
+    #[serde(skip_serializing)]
+    pub layout: LayoutConfig,
+    #[serde(skip_serializing)]
+    pub keys: KeysConfig,
+    #[serde(skip_serializing)]
+    pub schedule: Vec<ScheduleRule>,
+    #[serde(skip_serializing)]
+    pub timer: TimerConfig,
+    /// The settings of `[general]` that a theme would override, if set explicitly in the file.
+    #[serde(skip)]
+    pub explicit: Theme,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct GeneralConfig {
-    pub color: Color,
+    pub theme: Option<String>,
+    pub color: Palette,
+    pub gradient: Gradient,
+    pub animation: Animation,
+    pub animation_period: u64,
+    pub transition: Transition,
+    pub transition_duration: u64,
+    pub color_depth: ColorDepth,
     pub interval: u64,
     pub blink: bool,
     pub bold: bool,
+    pub layout: Layout,
+    pub plain: bool,
+    pub fill: char,
+}
+
+impl GeneralConfig {
+    pub fn fill(&self) -> Option<char> {
+        self.plain.then_some(self.fill)
+    }
 }
 
 impl Default for GeneralConfig {
     fn default() -> Self {
         Self {
+            theme: None,
             interval: 200,
-            color: Color::default(),
+            color: Palette::default(),
+            gradient: Gradient::default(),
+            animation: Animation::default(),
+            animation_period: 10000,
+            transition: Transition::default(),
+            transition_duration: 300,
+            color_depth: ColorDepth::default(),
             blink: false,
             bold: false,
+            layout: Layout::default(),
+            plain: Color::is_disabled(),
+            fill: '#',
         }
     }
 }
 
-#[derive(Default, Deserialize)]
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ColorsConfig {
+    pub digits: Option<Palette>,
+    pub colon: Option<Color>,
+    pub text: Option<Color>,
+    pub hint: Option<Color>,
+    pub background: Option<Color>,
+}
+
+#[derive(Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct PositionConfig {
     #[serde(rename = "horizontal")]
     pub x: Position,
     #[serde(rename = "vertical")]
     pub y: Position,
+    pub margin: Margin,
+    pub burn_in: u64,
+    pub bounce: bool,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BorderConfig {
+    pub style: BorderStyle,
+    pub title: Option<String>,
+    pub shadow: bool,
+    pub color: Option<Color>,
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LayoutConfig {
+    pub split: Split,
+    pub panes: Vec<Pane>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct DateConfig {
     pub fmt: String,
     pub use_12h: bool,
     pub utc: bool,
+    #[serde(
+        deserialize_with = "deserialize_time_zone",
+        serialize_with = "serialize_time_zone"
+    )]
+    pub timezone: Option<Tz>,
     pub hide_seconds: bool,
 }
 
@@ -60,38 +149,78 @@ impl Default for DateConfig {
             fmt: "%d-%m-%Y".to_string(),
             use_12h: false,
             utc: false,
+            timezone: None,
             hide_seconds: false,
         }
     }
 }
 
+#[derive(Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TimerConfig {
+    pub thresholds: BTreeMap<Threshold, ThresholdStyle>,
+    pub flash: u64,
+}
+
+impl TimerConfig {
+    pub fn thresholds(self) -> Vec<(Duration, Theme)> {
+        self.thresholds
+            .into_iter()
+            .rev()
+            .map(|(Threshold(duration), ThresholdStyle(style))| (duration, style))
+            .collect()
+    }
+}
+
 impl Config {
-    pub fn parse() -> Result<Self, Error> {
-        let path = match env::var("CONF_PATH") {
+    pub fn uses_auto_color(&self) -> bool {
+        let ColorsConfig {
+            digits,
+            colon,
+            text,
+            hint,
+            background,
+        } = &self.colors;
+
+        self.general.color.contains(&Color::Auto)
+            || digits
+                .as_ref()
+                .is_some_and(|digits| digits.contains(&Color::Auto))
+            || [colon, text, hint, background]
+                .into_iter()
+                .any(|color| matches!(color, Some(Color::Auto)))
+    }
+
+    /// Returns the path of the configuration file. Unless `existing` is set,
+    /// the default location is returned even if there is no file yet.
+    pub fn path(existing: bool) -> Result<Option<String>, Error> {
+        match env::var("CONF_PATH") {
             Ok(path) => match path.as_str() {
-                "None" => None,
-                _ => Some(path),
+                "None" => Ok(None),
+                _ => Ok(Some(path)),
             },
             Err(VarError::NotUnicode(path)) => {
-                return Err(Error::NonUnicodePath(path.display().to_string()));
+                Err(Error::NonUnicodePath(path.display().to_string()))
             }
             Err(VarError::NotPresent) => match dirs::config_local_dir() {
                 Some(config_local_dir) => {
                     match config_local_dir.join("clock-rs").join("conf.toml").to_str() {
-                        Some(path) if Path::new(path).exists() => Some(path.to_string()),
-                        Some(_) => None,
-                        None => {
-                            return Err(Error::NonUnicodePath(
-                                config_local_dir.display().to_string(),
-                            ))
+                        Some(path) if !existing || Path::new(path).exists() => {
+                            Ok(Some(path.to_string()))
                         }
+                        Some(_) => Ok(None),
+                        None => Err(Error::NonUnicodePath(
+                            config_local_dir.display().to_string(),
+                        )),
                     }
                 }
-                None => None,
+                None => Ok(None),
             },
-        };
+        }
+    }
 
-        let Some(file_path) = path else {
+    pub fn parse() -> Result<Self, Error> {
+        let Some(file_path) = Self::path(true)? else {
             return Ok(Config::default());
         };
 
@@ -100,9 +229,348 @@ impl Config {
             err: err.to_string(),
         })?;
 
-        toml::from_str(&config_str).map_err(|err| Error::ParseToml {
-            path: file_path,
+        #[derive(Default, Deserialize)]
+        #[serde(default)]
+        struct Explicit {
+            general: Theme,
+        }
+
+        let parse_error = |err: toml::de::Error| Error::ParseToml {
+            path: file_path.clone(),
             err: err.to_string(),
-        })
+        };
+        let mut config: Self = toml::from_str(&config_str).map_err(parse_error)?;
+        let explicit: Explicit = toml::from_str(&config_str).map_err(parse_error)?;
+
+        config.explicit = explicit.general;
+
+        config.keys.validate()?;
+
+        Ok(config)
+    }
+
+    /// Writes the settings that differ from `saved` to the configuration file,
+    /// keeping the comments and ordering of the rest of the file intact. Returns the path of the file.
+    /// With `pane`, the settings are written to that entry of `[[layout.panes]]` instead.
+    pub fn save(&self, saved: &Self, pane: Option<usize>) -> Result<String, Error> {
+        let path = Self::path(false)?.ok_or(Error::NoConfigPath)?;
+
+        let mut document = match fs::read_to_string(&path) {
+            Ok(config_str) => {
+                config_str
+                    .parse::<DocumentMut>()
+                    .map_err(|err| Error::ParseToml {
+                        path: path.clone(),
+                        err: err.to_string(),
+                    })?
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => DocumentMut::new(),
+            Err(err) => {
+                return Err(Error::ReadFile {
+                    path,
+                    err: err.to_string(),
+                })
+            }
+        };
+
+        let write_error = |err: String| Error::WriteFile {
+            path: path.clone(),
+            err,
+        };
+        let current = toml::Table::try_from(self).map_err(|err| write_error(err.to_string()))?;
+        let saved = toml::Table::try_from(saved).map_err(|err| write_error(err.to_string()))?;
+
+        let mut changes = Vec::new();
+
+        for (table, fields) in &current {
+            let (Some(fields), saved_fields) = (
+                fields.as_table(),
+                saved.get(table).and_then(toml::Value::as_table),
+            ) else {
+                continue;
+            };
+
+            let keys = fields
+                .keys()
+                .chain(saved_fields.into_iter().flat_map(toml::Table::keys))
+                .collect::<BTreeSet<_>>();
+
+            for key in keys {
+                let field = fields.get(key);
+
+                if field != saved_fields.and_then(|saved_fields| saved_fields.get(key)) {
+                    changes.push((table.as_str(), key.as_str(), field));
+                }
+            }
+        }
+
+        for (table, key, field) in changes {
+            let Some(index) = pane else {
+                let entry = document
+                    .entry(table)
+                    .or_insert(toml_edit::table())
+                    .as_table_like_mut()
+                    .ok_or_else(|| write_error(format!("`{table}` is not a table")))?;
+
+                set(entry, key, field).map_err(write_error)?;
+                continue;
+            };
+
+            let setting = || Error::PaneSetting(format!("{table}.{key}"));
+            let (name, subkey) = Pane::field(table, key).ok_or_else(setting)?;
+
+            // Panes only override settings, so a removed value would fall back to the top-level one.
+            if field.is_none()
+                && name != "border"
+                && document.get(table).and_then(|item| item.get(key)).is_some()
+            {
+                return Err(setting());
+            }
+
+            let pane = document
+                .get_mut("layout")
+                .and_then(|layout| layout.get_mut("panes"))
+                .and_then(Item::as_array_of_tables_mut)
+                .and_then(|panes| panes.get_mut(index))
+                .ok_or_else(|| {
+                    write_error(format!(
+                        "pane {} is not a `[[layout.panes]]` table",
+                        index + 1
+                    ))
+                })?;
+
+            match subkey {
+                Some(subkey) => {
+                    let entry = pane
+                        .entry(name)
+                        .or_insert(toml_edit::table())
+                        .as_table_like_mut()
+                        .ok_or_else(|| {
+                            write_error(format!("`{name}` of pane {} is not a table", index + 1))
+                        })?;
+
+                    set(entry, subkey, field)
+                }
+                // A pane replaces the whole border, so all of its settings are written.
+                None if name == "border" => set(pane, name, current.get(name)),
+                None => set(pane, name, field),
+            }
+            .map_err(write_error)?;
+        }
+
+        let write_error = |err: io::Error| Error::WriteFile {
+            path: path.clone(),
+            err: err.to_string(),
+        };
+
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent).map_err(write_error)?;
+        }
+
+        fs::write(&path, document.to_string()).map_err(write_error)?;
+
+        Ok(path)
+    }
+
+    pub fn apply_theme(&mut self) -> Result<(), Error> {
+        if let Some(theme) = &self.general.theme {
+            Theme::load(theme)?
+                .overridden_by(self.explicit.clone())
+                .apply(self);
+        }
+
+        for rule in &mut self.schedule {
+            if let Some(theme) = rule.theme.take() {
+                rule.style = Theme::load(&theme)?.overridden_by(mem::take(&mut rule.style));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sets `key` to `field` or removes it, keeping the comments around a previous value.
+fn set(table: &mut dyn TableLike, key: &str, field: Option<&toml::Value>) -> Result<(), String> {
+    let Some(field) = field else {
+        table.remove(key);
+        return Ok(());
+    };
+
+    let mut value = field
+        .to_string()
+        .parse::<toml_edit::Value>()
+        .map_err(|err| err.to_string())?;
+
+    if let Some(previous) = table.get(key).and_then(Item::as_value) {
+        *value.decor_mut() = previous.decor().clone();
+    }
+
+    table.insert(key, Item::Value(value));
+
+    Ok(())
+}
+
+pub fn deserialize_time_zone<'de, D>(deserializer: D) -> Result<Option<Tz>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let string = String::deserialize(deserializer)?;
+
+    string
+        .parse()
+        .map(Some)
+        .map_err(|_| de::Error::custom(format!("unknown time zone `{string}`")))
+}
+
+fn serialize_time_zone<S>(time_zone: &Option<Tz>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match time_zone {
+        Some(time_zone) => serializer.serialize_str(time_zone.name()),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, sync::Mutex};
+
+    use super::*;
+
+    // `CONF_PATH` is shared by the whole process, so the tests must not run at the same time.
+    static CONF_PATH: Mutex<()> = Mutex::new(());
+
+    /// Loads `config_str` from a temporary file, applies `change` to the configuration
+    /// of `pane` or the whole file and saves it, returning the result and the new file.
+    fn save_pane(
+        name: &str,
+        config_str: &str,
+        pane: Option<usize>,
+        change: impl FnOnce(&mut Config),
+    ) -> (Result<String, Error>, String) {
+        let _lock = CONF_PATH.lock().unwrap_or_else(|err| err.into_inner());
+        let path = env::temp_dir().join(format!("clock-rs-{}-{name}.toml", std::process::id()));
+
+        fs::write(&path, config_str).unwrap();
+        env::set_var("CONF_PATH", &path);
+
+        let raw_config = Config::parse().unwrap();
+        let saved = match pane {
+            Some(index) => raw_config.layout.panes[index]
+                .config(&raw_config, None)
+                .unwrap(),
+            None => raw_config,
+        };
+        let mut config = saved.clone();
+
+        change(&mut config);
+
+        let result = config.save(&saved, pane);
+        let config_str = fs::read_to_string(&path).unwrap();
+
+        env::remove_var("CONF_PATH");
+        fs::remove_file(&path).unwrap();
+
+        (result, config_str)
+    }
+
+    fn save(name: &str, config_str: &str, change: impl FnOnce(&mut Config)) -> String {
+        let (result, config_str) = save_pane(name, config_str, None, change);
+
+        assert!(result.is_ok());
+
+        config_str
+    }
+
+    #[test]
+    fn keeps_comments_and_order() {
+        let config_str = save(
+            "comments",
+            "# clock\n[general]\ncolor = \"cyan\" # favourite\nbold = false\n\n# date\n[date]\nfmt = \"%Y\"\n",
+            |config| {
+                config.general.color = Palette::from(Color::Red);
+                config.date.use_12h = true;
+            },
+        );
+
+        assert_eq!(
+            config_str,
+            "# clock\n[general]\ncolor = \"red\" # favourite\nbold = false\n\n# date\n[date]\nfmt = \"%Y\"\nuse_12h = true\n"
+        );
+    }
+
+    #[test]
+    fn leaves_unchanged_keys_untouched() {
+        let original = "[general]\ninterval   =   250 # slow\nblink = true\n\n[position]\nhorizontal = \"end-2\"\n";
+        let config_str = save("unchanged", original, |_| ());
+
+        assert_eq!(config_str, original);
+    }
+
+    #[test]
+    fn removes_cleared_title() {
+        let config_str = save(
+            "title",
+            "[border]\nstyle = \"rounded\"\ntitle = \"Hi\" # greeting\nshadow = true\n",
+            |config| config.border.title = None,
+        );
+
+        assert_eq!(config_str, "[border]\nstyle = \"rounded\"\nshadow = true\n");
+    }
+
+    #[test]
+    fn writes_margin_as_inline_table() {
+        let config_str = save("margin", "[position]\nmargin = 1 # tight\n", |config| {
+            config.position.margin = "1,2".parse().unwrap();
+        });
+        let document = config_str.parse::<DocumentMut>().unwrap();
+        let margin = document["position"]["margin"].as_inline_table().unwrap();
+
+        assert!(config_str.ends_with(" # tight\n"));
+        assert_eq!(
+            ["top", "right", "bottom", "left"].map(|side| margin[side].as_integer()),
+            [Some(1), Some(2), Some(1), Some(2)]
+        );
+
+        let config_str = save("uniform-margin", &config_str, |config| {
+            config.position.margin = "3".parse().unwrap();
+        });
+
+        assert_eq!(config_str, "[position]\nmargin = 3 # tight\n");
+    }
+
+    const PANES: &str = "[general]\ncolor = \"green\"\n\n[[layout.panes]]\ncolor = \"red\" # left\n\n[[layout.panes]]\nmode = \"stopwatch\"\n";
+
+    #[test]
+    fn writes_pane_settings_to_the_pane() {
+        let (result, config_str) = save_pane("pane", PANES, Some(0), |config| {
+            config.general.color = Palette::from(Color::Blue);
+            config.border.style = BorderStyle::Rounded;
+        });
+
+        assert!(result.is_ok());
+        assert_eq!(
+            config_str,
+            "[general]\ncolor = \"green\"\n\n[[layout.panes]]\ncolor = \"blue\" # left\nborder = { shadow = false, style = \"rounded\" }\n\n[[layout.panes]]\nmode = \"stopwatch\"\n"
+        );
+
+        let (result, config_str) = save_pane("second-pane", PANES, Some(1), |config| {
+            config.general.color = Palette::from(Color::Blue);
+        });
+
+        assert!(result.is_ok());
+        assert!(config_str.ends_with("[[layout.panes]]\nmode = \"stopwatch\"\ncolor = \"blue\"\n"));
+    }
+
+    #[test]
+    fn rejects_settings_that_panes_cannot_hold() {
+        let (result, config_str) = save_pane("pane-bold", PANES, Some(0), |config| {
+            config.general.color = Palette::from(Color::Blue);
+            config.general.bold = true;
+        });
+
+        assert!(matches!(result, Err(Error::PaneSetting(setting)) if setting == "general.bold"));
+        assert_eq!(config_str, PANES);
     }
 }
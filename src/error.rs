@@ -20,8 +20,24 @@ pub enum Error {
     NonUnicodePath(String),
     #[error("failed to read file `{path}`: {err}")]
     ReadFile { path: String, err: String },
+    #[error("failed to write file `{path}`: {err}")]
+    WriteFile { path: String, err: String },
+    #[error("there is no configuration file to write to")]
+    NoConfigPath,
+    #[error("`{0}` cannot be saved for a single pane")]
+    PaneSetting(String),
     #[error("failed to parse configuration file `{path}`:\n{err}")]
     ParseToml { path: String, err: String },
+    #[error("theme `{0}` is neither a built-in theme nor a file in the themes directory")]
+    ThemeNotFound(String),
+    #[error("key `{key}` is bound to both `{first}` and `{second}`")]
+    KeyConflict {
+        key: String,
+        first: &'static str,
+        second: &'static str,
+    },
+    #[error("key `{key}` of `{action}` is reserved and cannot be rebound")]
+    KeyReserved { key: String, action: &'static str },
     #[error("IO error: {0}")]
     Io(#[from] io::Error),
 }
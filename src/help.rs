@@ -0,0 +1,82 @@
+use std::io::{BufWriter, StdoutLock, Write};
+
+use crossterm::{cursor::MoveTo, queue};
+
+use crate::{border::BorderStyle, color::Color, error::Error};
+
+const TITLE: &str = "Keys";
+const GAP: usize = 3;
+
+pub fn fmt(
+    w: &mut BufWriter<StdoutLock<'_>>,
+    bindings: &[(String, &str)],
+    (width, height): (u16, u16),
+    reset: &str,
+    plain: bool,
+) -> Result<(), Error> {
+    let Some(chars) = BorderStyle::Rounded.chars() else {
+        return Ok(());
+    };
+
+    let keys_width = bindings
+        .iter()
+        .map(|(keys, _)| keys.chars().count())
+        .max()
+        .unwrap_or_default();
+    let actions_width = bindings
+        .iter()
+        .map(|(_, action)| action.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    let inner_width = (keys_width + GAP + actions_width + 2)
+        .max(TITLE.len() + 4)
+        .min((width as usize).saturating_sub(2));
+    let rows = bindings.len().min((height as usize).saturating_sub(2));
+
+    if inner_width < TITLE.len() + 4 || rows == 0 {
+        return Ok(());
+    }
+
+    let left = (width - inner_width as u16 - 2) / 2;
+    let top = (height - rows as u16 - 2) / 2;
+    let bold = if plain { "" } else { Color::BOLD };
+
+    let title_padding = inner_width - TITLE.len() - 3;
+    let horizontal = chars.horizontal.to_string();
+
+    queue!(w, MoveTo(left, top))?;
+    write!(
+        w,
+        "{reset}{}{} {bold}{TITLE}{reset} {}{}",
+        chars.top_left,
+        chars.horizontal,
+        horizontal.repeat(title_padding),
+        chars.top_right
+    )?;
+
+    for (i, (keys, action)) in bindings.iter().take(rows).enumerate() {
+        let line = format!("{keys:<keys_width$}{:GAP$}{action}", "");
+        let line: String = line.chars().take(inner_width - 2).collect();
+        let (keys, action) = line.split_at(line.len().min(keys.len()));
+        let padding = inner_width - 2 - line.chars().count();
+
+        queue!(w, MoveTo(left, top + 1 + i as u16))?;
+        write!(
+            w,
+            "{}{reset} {bold}{keys}{reset}{action}{:padding$} {}",
+            chars.vertical, "", chars.vertical
+        )?;
+    }
+
+    queue!(w, MoveTo(left, top + 1 + rows as u16))?;
+    write!(
+        w,
+        "{}{}{}{reset}",
+        chars.bottom_left,
+        horizontal.repeat(inner_width),
+        chars.bottom_right
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,437 @@
+use std::{fmt, str::FromStr};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{de, Deserialize, Deserializer};
+
+use crate::{command::Toggle, error::Error};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Action {
+    Quit,
+    Pause,
+    Restart,
+    Reload,
+    Lap,
+    ToggleSeconds,
+    Toggle12h,
+    ToggleBlink,
+    ToggleBold,
+    Save,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(default)]
+pub struct KeysConfig {
+    #[serde(deserialize_with = "deserialize_chords")]
+    pub quit: Vec<KeyChord>,
+    #[serde(deserialize_with = "deserialize_chords")]
+    pub pause: Vec<KeyChord>,
+    #[serde(deserialize_with = "deserialize_chords")]
+    pub restart: Vec<KeyChord>,
+    #[serde(deserialize_with = "deserialize_chords")]
+    pub reload: Vec<KeyChord>,
+    #[serde(deserialize_with = "deserialize_chords")]
+    pub lap: Vec<KeyChord>,
+    #[serde(deserialize_with = "deserialize_chords")]
+    pub toggle_seconds: Vec<KeyChord>,
+    #[serde(deserialize_with = "deserialize_chords")]
+    pub toggle_12h: Vec<KeyChord>,
+    #[serde(deserialize_with = "deserialize_chords")]
+    pub toggle_blink: Vec<KeyChord>,
+    #[serde(deserialize_with = "deserialize_chords")]
+    pub toggle_bold: Vec<KeyChord>,
+    #[serde(deserialize_with = "deserialize_chords")]
+    pub save: Vec<KeyChord>,
+}
+
+impl Action {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::Pause => "pause",
+            Self::Restart => "restart",
+            Self::Reload => "reload",
+            Self::Lap => "lap",
+            Self::ToggleSeconds => "toggle_seconds",
+            Self::Toggle12h => "toggle_12h",
+            Self::ToggleBlink => "toggle_blink",
+            Self::ToggleBold => "toggle_bold",
+            Self::Save => "save",
+        }
+    }
+
+    pub fn toggle(&self) -> Option<Toggle> {
+        match self {
+            Self::ToggleSeconds => Some(Toggle::Seconds),
+            Self::Toggle12h => Some(Toggle::Use12h),
+            Self::ToggleBlink => Some(Toggle::Blink),
+            Self::ToggleBold => Some(Toggle::Bold),
+            _ => None,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Quit => "Quit",
+            Self::Pause => "Toggle pause",
+            Self::Restart => "Restart",
+            Self::Reload => "Reload the configuration",
+            Self::Lap => "Record a lap",
+            Self::ToggleSeconds => "Show or hide the seconds",
+            Self::Toggle12h => "Switch between the 12h and 24h format",
+            Self::ToggleBlink => "Toggle the blinking colon",
+            Self::ToggleBold => "Toggle bold text",
+            Self::Save => "Save the settings to the configuration",
+        }
+    }
+}
+
+impl KeyChord {
+    const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    /// Returns the pressed chord, and for characters a fallback without Shift,
+    /// so that `p` also matches `P` unless `P` is bound itself.
+    fn from_event(key_event: &KeyEvent) -> (Self, Option<Self>) {
+        let KeyCode::Char(character) = key_event.code else {
+            return (Self::new(key_event.code, key_event.modifiers), None);
+        };
+
+        let chord = Self::from_char(character, key_event.modifiers);
+        let fallback = Self::new(chord.code, chord.modifiers - KeyModifiers::SHIFT);
+
+        (chord, Some(fallback))
+    }
+
+    fn from_char(character: char, mut modifiers: KeyModifiers) -> Self {
+        if character.is_uppercase() {
+            modifiers |= KeyModifiers::SHIFT;
+        }
+
+        let character = character.to_lowercase().next().unwrap_or(character);
+
+        Self::new(KeyCode::Char(character), modifiers)
+    }
+
+    /// Keys that are handled before any configurable action and therefore cannot be bound.
+    fn is_reserved(&self) -> bool {
+        match self.code {
+            KeyCode::Tab | KeyCode::BackTab => true,
+            KeyCode::Char('0'..='9' | '+' | '=' | '-' | '_' | ':' | '?') => {
+                !self.modifiers.contains(KeyModifiers::CONTROL)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl KeysConfig {
+    pub fn chords(&self) -> [(Action, &[KeyChord]); 10] {
+        [
+            (Action::Quit, &self.quit),
+            (Action::Pause, &self.pause),
+            (Action::Restart, &self.restart),
+            (Action::Reload, &self.reload),
+            (Action::Lap, &self.lap),
+            (Action::ToggleSeconds, &self.toggle_seconds),
+            (Action::Toggle12h, &self.toggle_12h),
+            (Action::ToggleBlink, &self.toggle_blink),
+            (Action::ToggleBold, &self.toggle_bold),
+            (Action::Save, &self.save),
+        ]
+    }
+
+    pub fn action(&self, key_event: &KeyEvent) -> Option<Action> {
+        let find = |chord: KeyChord| {
+            self.chords()
+                .into_iter()
+                .find(|(_, chords)| chords.contains(&chord))
+                .map(|(action, _)| action)
+        };
+
+        let (chord, fallback) = KeyChord::from_event(key_event);
+
+        find(chord).or_else(|| find(fallback?))
+    }
+
+    /// Returns the keys bound to the action joined by slashes, or `None` if it is unbound.
+    pub fn label(&self, action: Action) -> Option<String> {
+        let (_, chords) = self
+            .chords()
+            .into_iter()
+            .find(|(other, _)| *other == action)?;
+
+        let label = chords
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" / ");
+
+        (!label.is_empty()).then_some(label)
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        let chords = self.chords();
+        let bound = chords
+            .iter()
+            .flat_map(|(action, chords)| chords.iter().map(move |chord| (action, chord)));
+
+        for (i, (action, chord)) in bound.clone().enumerate() {
+            if chord.is_reserved() {
+                return Err(Error::KeyReserved {
+                    key: chord.to_string(),
+                    action: action.name(),
+                });
+            }
+
+            if let Some((other, _)) = bound.clone().skip(i + 1).find(|(_, other)| *other == chord) {
+                return Err(Error::KeyConflict {
+                    key: chord.to_string(),
+                    first: action.name(),
+                    second: other.name(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for KeysConfig {
+    fn default() -> Self {
+        let key = |character| KeyChord::new(KeyCode::Char(character), KeyModifiers::NONE);
+        let ctrl = |character| KeyChord::new(KeyCode::Char(character), KeyModifiers::CONTROL);
+
+        Self {
+            quit: vec![
+                KeyChord::new(KeyCode::Esc, KeyModifiers::NONE),
+                key('q'),
+                ctrl('c'),
+            ],
+            pause: vec![key('p')],
+            restart: vec![key('r')],
+            reload: vec![ctrl('r')],
+            lap: vec![key('l')],
+            toggle_seconds: vec![key('s')],
+            toggle_12h: vec![key('t')],
+            toggle_blink: vec![key('b')],
+            toggle_bold: vec![KeyChord::new(KeyCode::Char('b'), KeyModifiers::SHIFT)],
+            save: vec![ctrl('s')],
+        }
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (modifier_names, key) = match s.strip_suffix('+') {
+            Some(rest) if rest.is_empty() || rest.ends_with('+') => {
+                (rest.strip_suffix('+').unwrap_or(rest), "+")
+            }
+            _ => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+
+        let mut modifiers = KeyModifiers::NONE;
+
+        for modifier in modifier_names.split('+').filter(|name| !name.is_empty()) {
+            modifiers |= match modifier.trim().to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("invalid modifier `{modifier}` in key `{s}`")),
+            };
+        }
+
+        let key = key.trim();
+        let code = match key.to_lowercase().as_str() {
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            name => match (
+                name.strip_prefix('f').map(str::parse::<u8>),
+                key.chars().count(),
+            ) {
+                (Some(Ok(number @ 1..=12)), _) => KeyCode::F(number),
+                (_, 1) => {
+                    let character = key.chars().next().unwrap_or_default();
+
+                    return Ok(Self::from_char(character, modifiers));
+                }
+                _ => return Err(format!("invalid key `{s}`")),
+            },
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl"),
+            (KeyModifiers::ALT, "Alt"),
+            (KeyModifiers::SHIFT, "Shift"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name} + ")?;
+            }
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(character) => write!(f, "{}", character.to_uppercase()),
+            KeyCode::F(number) => write!(f, "F{number}"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            code => write!(f, "{code}"),
+        }
+    }
+}
+
+fn deserialize_chords<'de, D>(deserializer: D) -> Result<Vec<KeyChord>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ChordsRepr {
+        Single(String),
+        List(Vec<String>),
+    }
+
+    let chords = match ChordsRepr::deserialize(deserializer)? {
+        ChordsRepr::Single(chord) => vec![chord],
+        ChordsRepr::List(chords) => chords,
+    };
+
+    chords
+        .iter()
+        .map(|chord| chord.parse())
+        .collect::<Result<_, _>>()
+        .map_err(de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(s: &str) -> KeyChord {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_modifiers_and_named_keys() {
+        assert_eq!(
+            chord("ctrl+r"),
+            KeyChord::new(KeyCode::Char('r'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            chord("Ctrl + Alt + Delete"),
+            KeyChord::new(KeyCode::Delete, KeyModifiers::CONTROL | KeyModifiers::ALT)
+        );
+        assert_eq!(
+            chord("esc"),
+            KeyChord::new(KeyCode::Esc, KeyModifiers::NONE)
+        );
+        assert_eq!(
+            chord("space"),
+            KeyChord::new(KeyCode::Char(' '), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            chord("f12"),
+            KeyChord::new(KeyCode::F(12), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            chord("ctrl++"),
+            KeyChord::new(KeyCode::Char('+'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            chord("+"),
+            KeyChord::new(KeyCode::Char('+'), KeyModifiers::NONE)
+        );
+    }
+
+    #[test]
+    fn uppercase_letters_imply_shift() {
+        assert_eq!(chord("B"), chord("shift+b"));
+        assert_eq!(chord("B").to_string(), "Shift + B");
+    }
+
+    #[test]
+    fn rejects_invalid_keys() {
+        assert!("hyper+a".parse::<KeyChord>().is_err());
+        assert!("f13".parse::<KeyChord>().is_err());
+        assert!("ab".parse::<KeyChord>().is_err());
+    }
+
+    #[test]
+    fn default_keys_are_valid() {
+        assert!(KeysConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_conflicting_keys() {
+        let keys = KeysConfig {
+            lap: vec![chord("p")],
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            keys.validate(),
+            Err(Error::KeyConflict {
+                first: "pause",
+                second: "lap",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_reserved_keys() {
+        for key in ["1", "=", "+", "_", ":", "?", "tab"] {
+            let keys = KeysConfig {
+                pause: vec![chord(key)],
+                ..Default::default()
+            };
+
+            assert!(
+                matches!(
+                    keys.validate(),
+                    Err(Error::KeyReserved {
+                        action: "pause",
+                        ..
+                    })
+                ),
+                "`{key}` should be reserved"
+            );
+        }
+
+        let keys = KeysConfig {
+            pause: vec![chord("ctrl+1")],
+            ..Default::default()
+        };
+
+        assert!(keys.validate().is_ok());
+    }
+}
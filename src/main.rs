@@ -1,20 +1,45 @@
+mod border;
 mod character;
 mod cli;
 mod clock;
 #[macro_use]
 mod color;
+mod command;
 mod config;
 mod error;
+mod help;
+mod keys;
+mod layout;
+mod pane;
 mod position;
+mod prompt;
+mod schedule;
 mod segment;
 mod state;
+mod terminal;
+mod theme;
+mod threshold;
+mod transition;
 
 use std::process;
 
-use crate::{color::Color, error::Error, state::State};
+use clap::Parser;
+
+use crate::{
+    cli::args::{Args, Mode},
+    color::Color,
+    error::Error,
+    state::State,
+};
 
 fn run() -> Result<(), Error> {
-    State::new()?.run()
+    let args = Args::parse();
+
+    if let Some(Mode::Themes(themes_args)) = args.mode {
+        return themes_args.run();
+    }
+
+    State::new(args)?.run()
 }
 
 fn main() {
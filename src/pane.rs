@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+use chrono_tz::Tz;
+use serde::{de, Deserialize, Deserializer};
+
+use crate::{
+    clock::{
+        counter::{Counter, CounterType},
+        mode::{ClockMode, ModeKind},
+        time_zone::TimeZone,
+    },
+    color::Palette,
+    config::{deserialize_time_zone, BorderConfig, ColorsConfig, Config},
+    error::Error,
+    position::Position,
+};
+
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Split {
+    #[default]
+    Rows,
+    Columns,
+}
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Pane {
+    pub mode: ModeKind,
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub duration: Option<Duration>,
+    pub theme: Option<String>,
+    pub color: Option<Palette>,
+    pub colors: ColorsConfig,
+    pub horizontal: Option<Position>,
+    pub vertical: Option<Position>,
+    pub border: Option<BorderConfig>,
+    pub fmt: Option<String>,
+    pub utc: Option<bool>,
+    #[serde(deserialize_with = "deserialize_time_zone")]
+    pub timezone: Option<Tz>,
+}
+
+impl Pane {
+    /// Returns the configuration of the pane, applying the theme before the pane's own settings
+    /// so that they are not overridden by it. `theme` takes precedence over the theme of the pane.
+    pub fn config(&self, config: &Config, theme: Option<&str>) -> Result<Config, Error> {
+        let mut config = config.clone();
+
+        if let Some(theme) = theme.or(self.theme.as_deref()) {
+            config.general.theme = Some(theme.to_string());
+        }
+
+        config.apply_theme()?;
+
+        if let Some(color) = &self.color {
+            config.general.color = color.clone();
+        }
+
+        let colors = &mut config.colors;
+
+        colors.digits = self.colors.digits.clone().or(colors.digits.take());
+        colors.colon = self.colors.colon.clone().or(colors.colon.take());
+        colors.text = self.colors.text.clone().or(colors.text.take());
+        colors.hint = self.colors.hint.clone().or(colors.hint.take());
+        colors.background = self.colors.background.clone().or(colors.background.take());
+
+        if let Some(horizontal) = self.horizontal {
+            config.position.x = horizontal;
+        }
+
+        if let Some(vertical) = self.vertical {
+            config.position.y = vertical;
+        }
+
+        if let Some(border) = &self.border {
+            config.border = border.clone();
+        }
+
+        if let Some(fmt) = &self.fmt {
+            config.date.fmt = fmt.clone();
+        }
+
+        if let Some(utc) = self.utc {
+            config.date.utc = utc;
+        }
+
+        if let Some(timezone) = self.timezone {
+            config.date.timezone = Some(timezone);
+        }
+
+        Ok(config)
+    }
+
+    /// Returns the field of a pane that overrides `key` of `table` in the configuration,
+    /// along with the key inside that field if it is a table.
+    pub fn field<'a>(table: &str, key: &'a str) -> Option<(&'static str, Option<&'a str>)> {
+        Some(match (table, key) {
+            ("general", "theme") => ("theme", None),
+            ("general", "color") => ("color", None),
+            ("colors", key) => ("colors", Some(key)),
+            ("position", "horizontal") => ("horizontal", None),
+            ("position", "vertical") => ("vertical", None),
+            ("border", _) => ("border", None),
+            ("date", "fmt") => ("fmt", None),
+            ("date", "utc") => ("utc", None),
+            ("date", "timezone") => ("timezone", None),
+            _ => return None,
+        })
+    }
+
+    pub fn clock_mode(&self, config: &Config) -> Result<ClockMode, Error> {
+        let ty = match self.mode {
+            ModeKind::Clock => {
+                return Ok(ClockMode::Time {
+                    time_zone: TimeZone::from_config(&config.date),
+                    date_format: config.date.fmt.clone(),
+                })
+            }
+            ModeKind::Stopwatch => CounterType::Stopwatch,
+            ModeKind::Timer => {
+                let duration = self
+                    .duration
+                    .unwrap_or(Duration::from_secs(Counter::DEFAULT_TIMER_DURATION));
+                let total_seconds = duration.as_secs();
+
+                if total_seconds > Counter::MAX_TIMER_DURATION {
+                    return Err(Error::TimerDurationTooLong {
+                        hours: total_seconds / 3600,
+                        minutes: total_seconds / 60 % 60,
+                        seconds: total_seconds % 60,
+                    });
+                }
+
+                CounterType::Timer {
+                    duration,
+                    kill: false,
+                }
+            }
+        };
+
+        Ok(ClockMode::Counter(Counter::new(ty)))
+    }
+}
+
+fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let string = String::deserialize(deserializer)?;
+
+    Counter::parse_duration(&string)
+        .map(Some)
+        .map_err(de::Error::custom)
+}
@@ -1,21 +1,323 @@
-use clap::ValueEnum;
-use serde::Deserialize;
+use std::str::FromStr;
 
-#[derive(Clone, Default, Deserialize, ValueEnum)]
-#[serde(rename_all = "lowercase")]
-pub enum Position {
+use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Anchor {
     Start,
     #[default]
     Center,
     End,
+    Percent(u8),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Position {
+    pub anchor: Anchor,
+    pub offset: i16,
+}
+
+#[derive(Clone, Copy)]
+pub struct Margin {
+    pub top: u16,
+    pub right: u16,
+    pub bottom: u16,
+    pub left: u16,
 }
 
 impl Position {
-    pub fn calculate(&self, len: u16, offset: u16) -> u16 {
-        match self {
-            Self::Start => 1,
-            Self::Center => (len / 2).saturating_sub(offset),
-            Self::End => len.saturating_sub(offset * 2 + 2),
+    pub fn calculate(&self, len: u16, size: u16, (start, end): (u16, u16)) -> u16 {
+        let available = len.saturating_sub(start.saturating_add(end).saturating_add(size));
+
+        let position = start.saturating_add(match self.anchor {
+            Anchor::Start => 0,
+            Anchor::Center => available / 2,
+            Anchor::End => available,
+            Anchor::Percent(percent) => (u32::from(available) * u32::from(percent) / 100) as u16,
+        });
+
+        position.saturating_add_signed(self.offset)
+    }
+}
+
+impl FromStr for Anchor {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "start" => Ok(Self::Start),
+            "center" => Ok(Self::Center),
+            "end" => Ok(Self::End),
+            s => match s.strip_suffix('%').map(|percent| percent.trim().parse::<u8>()) {
+                Some(Ok(percent)) if percent <= 100 => Ok(Self::Percent(percent)),
+                _ => Err(format!(
+                    "invalid position `{s}`, expected `start`, `center`, `end` or a percentage like `25%`"
+                )),
+            },
+        }
+    }
+}
+
+impl FromStr for Position {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let Some(index) = s.get(1..).and_then(|rest| rest.rfind(['+', '-'])) else {
+            return Ok(Self {
+                anchor: s.parse()?,
+                offset: 0,
+            });
+        };
+
+        let (anchor, offset) = s.split_at(index + 1);
+        let offset = offset
+            .trim_start_matches('+')
+            .parse()
+            .map_err(|_| format!("invalid position offset `{offset}`"))?;
+
+        Ok(Self {
+            anchor: anchor.parse()?,
+            offset,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Position {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum PositionRepr {
+            Anchor(String),
+            Table {
+                anchor: String,
+                #[serde(default)]
+                offset: i16,
+            },
+        }
+
+        match PositionRepr::deserialize(deserializer)? {
+            PositionRepr::Anchor(s) => s.parse().map_err(de::Error::custom),
+            PositionRepr::Table { anchor, offset } => Ok(Self {
+                anchor: anchor.parse().map_err(de::Error::custom)?,
+                offset,
+            }),
+        }
+    }
+}
+
+impl Serialize for Position {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let anchor = match self.anchor {
+            Anchor::Start => "start".to_string(),
+            Anchor::Center => "center".to_string(),
+            Anchor::End => "end".to_string(),
+            Anchor::Percent(percent) => format!("{percent}%"),
+        };
+
+        match self.offset {
+            0 => serializer.serialize_str(&anchor),
+            offset => serializer.collect_str(&format_args!("{anchor}{offset:+}")),
+        }
+    }
+}
+
+impl Default for Margin {
+    fn default() -> Self {
+        Self::uniform(1)
+    }
+}
+
+impl Margin {
+    fn uniform(margin: u16) -> Self {
+        Self {
+            top: margin,
+            right: margin,
+            bottom: margin,
+            left: margin,
+        }
+    }
+
+    pub fn horizontal(&self) -> (u16, u16) {
+        (self.left, self.right)
+    }
+
+    pub fn vertical(&self) -> (u16, u16) {
+        (self.top, self.bottom)
+    }
+}
+
+impl FromStr for Margin {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let values = s
+            .split(',')
+            .map(|value| value.trim().parse::<u16>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| format!("invalid margin `{s}`"))?;
+
+        match values[..] {
+            [margin] => Ok(Self::uniform(margin)),
+            [vertical, horizontal] => Ok(Self {
+                top: vertical,
+                right: horizontal,
+                bottom: vertical,
+                left: horizontal,
+            }),
+            [top, right, bottom, left] => Ok(Self {
+                top,
+                right,
+                bottom,
+                left,
+            }),
+            _ => Err(format!("invalid margin `{s}`, expected 1, 2 or 4 values")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Margin {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum MarginRepr {
+            Uniform(u16),
+            Sides {
+                #[serde(default = "one")]
+                top: u16,
+                #[serde(default = "one")]
+                right: u16,
+                #[serde(default = "one")]
+                bottom: u16,
+                #[serde(default = "one")]
+                left: u16,
+            },
+        }
+
+        fn one() -> u16 {
+            1
+        }
+
+        Ok(match MarginRepr::deserialize(deserializer)? {
+            MarginRepr::Uniform(margin) => Self::uniform(margin),
+            MarginRepr::Sides {
+                top,
+                right,
+                bottom,
+                left,
+            } => Self {
+                top,
+                right,
+                bottom,
+                left,
+            },
+        })
+    }
+}
+
+impl Serialize for Margin {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let Self {
+            top,
+            right,
+            bottom,
+            left,
+        } = *self;
+
+        if [right, bottom, left].iter().all(|&side| side == top) {
+            return serializer.serialize_u16(top);
         }
+
+        let mut sides = serializer.serialize_struct("Margin", 4)?;
+
+        sides.serialize_field("top", &top)?;
+        sides.serialize_field("right", &right)?;
+        sides.serialize_field("bottom", &bottom)?;
+        sides.serialize_field("left", &left)?;
+        sides.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(s: &str) -> Position {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_anchors() {
+        assert_eq!(position("start").anchor, Anchor::Start);
+        assert_eq!(position(" Center ").anchor, Anchor::Center);
+        assert_eq!(position("END").anchor, Anchor::End);
+        assert_eq!(position("25%").anchor, Anchor::Percent(25));
+        assert_eq!(position("100 %").anchor, Anchor::Percent(100));
+    }
+
+    #[test]
+    fn parses_offsets() {
+        assert_eq!(
+            position("end-2"),
+            Position {
+                anchor: Anchor::End,
+                offset: -2
+            }
+        );
+        assert_eq!(
+            position("center+3"),
+            Position {
+                anchor: Anchor::Center,
+                offset: 3
+            }
+        );
+        assert_eq!(
+            position("50%-1"),
+            Position {
+                anchor: Anchor::Percent(50),
+                offset: -1
+            }
+        );
+        assert_eq!(position("start").offset, 0);
+    }
+
+    #[test]
+    fn rejects_invalid_positions() {
+        for s in ["middle", "101%", "-5", "end+x", "end-99999", "%"] {
+            assert!(s.parse::<Position>().is_err(), "`{s}` should be rejected");
+        }
+    }
+
+    #[test]
+    fn calculates_positions_within_the_margins() {
+        assert_eq!(position("start").calculate(100, 20, (2, 3)), 2);
+        assert_eq!(position("end").calculate(100, 20, (2, 3)), 77);
+        assert_eq!(position("center").calculate(100, 20, (2, 3)), 39);
+        assert_eq!(position("50%+1").calculate(100, 20, (2, 3)), 40);
+        assert_eq!(position("end").calculate(100, 20, (40000, 40000)), 40000);
+    }
+
+    #[test]
+    fn parses_margins() {
+        let sides = |margin: Margin| [margin.top, margin.right, margin.bottom, margin.left];
+
+        assert_eq!(sides("3".parse().unwrap()), [3, 3, 3, 3]);
+        assert_eq!(sides("1,2".parse().unwrap()), [1, 2, 1, 2]);
+        assert_eq!(sides("1, 2, 3, 4".parse().unwrap()), [1, 2, 3, 4]);
+        assert!("1,2,3".parse::<Margin>().is_err());
+        assert!("a".parse::<Margin>().is_err());
     }
 }
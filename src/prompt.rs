@@ -0,0 +1,137 @@
+use std::io::{BufWriter, StdoutLock, Write};
+
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    terminal::{Clear, ClearType},
+};
+
+use crate::{color::Color, command::Command, error::Error};
+
+#[derive(Default)]
+pub struct Prompt {
+    input: Option<String>,
+    message: Option<Result<String, String>>,
+}
+
+impl Prompt {
+    pub fn open(&mut self) {
+        self.input = Some(String::new());
+    }
+
+    pub fn close(&mut self) {
+        self.input = None;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.input.is_some()
+    }
+
+    pub fn insert(&mut self, character: char) {
+        if let Some(input) = &mut self.input {
+            input.push(character);
+        }
+    }
+
+    pub fn erase(&mut self) {
+        match &mut self.input {
+            Some(input) if !input.is_empty() => {
+                input.pop();
+            }
+            _ => self.close(),
+        }
+    }
+
+    pub fn submit(&mut self) -> Option<String> {
+        self.input.take()
+    }
+
+    pub fn show(&mut self, message: Result<String, String>) {
+        self.message = Some(message);
+    }
+
+    /// Hides the current message, returning whether there was one to hide.
+    pub fn dismiss(&mut self) -> bool {
+        self.message.take().is_some()
+    }
+
+    pub fn complete(&mut self) {
+        let Some(input) = &mut self.input else {
+            return;
+        };
+
+        let (start, candidates) = Command::complete(input);
+
+        match &candidates[..] {
+            [] => (),
+            [candidate] => {
+                input.truncate(start);
+                input.push_str(candidate);
+                input.push(' ');
+            }
+            [first, ..] => {
+                let prefix_len = first
+                    .char_indices()
+                    .map(|(index, character)| index + character.len_utf8())
+                    .take_while(|&end| {
+                        candidates.iter().all(|candidate| {
+                            candidate
+                                .get(..end)
+                                .is_some_and(|prefix| prefix.eq_ignore_ascii_case(&first[..end]))
+                        })
+                    })
+                    .last()
+                    .unwrap_or_default();
+
+                if prefix_len > input.len() - start {
+                    input.truncate(start);
+                    input.push_str(&first[..prefix_len]);
+                }
+
+                self.message = Some(Ok(candidates.join("  ")));
+            }
+        }
+    }
+
+    pub fn fmt(
+        &self,
+        w: &mut BufWriter<StdoutLock<'_>>,
+        (width, height): (u16, u16),
+        reset: &str,
+        plain: bool,
+    ) -> Result<(), Error> {
+        let bottom = height.saturating_sub(1);
+
+        if let Some(message) = &self.message {
+            let row = match self.input {
+                Some(_) => bottom.saturating_sub(1),
+                None => bottom,
+            };
+            let (style, text) = match message {
+                Err(text) if !plain => (Color::Red.foreground(), text),
+                Ok(text) | Err(text) => (String::new(), text),
+            };
+            let text: String = text.chars().take(width as usize).collect();
+
+            queue!(w, MoveTo(0, row))?;
+            write!(w, "{reset}")?;
+            queue!(w, Clear(ClearType::CurrentLine))?;
+            write!(w, "{style}{text}{reset}")?;
+        }
+
+        if let Some(input) = &self.input {
+            let visible = width.saturating_sub(2) as usize;
+            let skip = input.chars().count().saturating_sub(visible);
+            let input: String = input.chars().skip(skip).collect();
+
+            queue!(w, MoveTo(0, bottom))?;
+            write!(w, "{reset}")?;
+            queue!(w, Clear(ClearType::CurrentLine))?;
+            let cursor = if plain { "_" } else { Color::REVERSE };
+
+            write!(w, ":{input}{cursor} {reset}")?;
+        }
+
+        Ok(())
+    }
+}
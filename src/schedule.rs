@@ -0,0 +1,91 @@
+use chrono::NaiveTime;
+use serde::{de, Deserialize, Deserializer};
+
+use crate::theme::Theme;
+
+#[derive(Clone, Deserialize)]
+pub struct ScheduleRule {
+    #[serde(deserialize_with = "deserialize_time")]
+    pub from: NaiveTime,
+    #[serde(deserialize_with = "deserialize_time")]
+    pub to: NaiveTime,
+    pub theme: Option<String>,
+    #[serde(flatten)]
+    pub style: Theme,
+}
+
+impl ScheduleRule {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.from <= self.to {
+            self.from <= time && time < self.to
+        } else {
+            self.from <= time || time < self.to
+        }
+    }
+}
+
+fn deserialize_time<'de, D>(deserializer: D) -> Result<NaiveTime, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let string = String::deserialize(deserializer)?;
+
+    NaiveTime::parse_from_str(&string, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(&string, "%H:%M:%S"))
+        .map_err(|_| de::Error::custom(format!("expected format `HH:MM`, found `{string}`")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(from: &str, to: &str) -> ScheduleRule {
+        toml::from_str(&format!("from = \"{from}\"\nto = \"{to}\"")).unwrap()
+    }
+
+    fn contains(rule: &ScheduleRule, time: &str) -> bool {
+        rule.contains(NaiveTime::parse_from_str(time, "%H:%M:%S").unwrap())
+    }
+
+    #[test]
+    fn contains_times_within_a_day() {
+        let rule = rule("08:00", "17:30");
+
+        assert!(contains(&rule, "08:00:00"));
+        assert!(contains(&rule, "12:00:00"));
+        assert!(contains(&rule, "17:29:59"));
+        assert!(!contains(&rule, "17:30:00"));
+        assert!(!contains(&rule, "07:59:59"));
+    }
+
+    #[test]
+    fn wraps_around_midnight() {
+        let rule = rule("22:00", "06:00");
+
+        assert!(contains(&rule, "22:00:00"));
+        assert!(contains(&rule, "23:59:59"));
+        assert!(contains(&rule, "00:00:00"));
+        assert!(contains(&rule, "05:59:59"));
+        assert!(!contains(&rule, "06:00:00"));
+        assert!(!contains(&rule, "12:00:00"));
+        assert!(!contains(&rule, "21:59:59"));
+    }
+
+    #[test]
+    fn ends_at_midnight() {
+        let rule = rule("20:00", "00:00");
+
+        assert!(contains(&rule, "20:00:00"));
+        assert!(contains(&rule, "23:59:59"));
+        assert!(!contains(&rule, "00:00:00"));
+        assert!(!contains(&rule, "19:59:59"));
+    }
+
+    #[test]
+    fn parses_seconds() {
+        let rule = rule("06:00:30", "07:00");
+
+        assert!(!contains(&rule, "06:00:29"));
+        assert!(contains(&rule, "06:00:30"));
+    }
+}
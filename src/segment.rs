@@ -10,17 +10,64 @@ pub enum Segment {
 }
 
 impl Segment {
-    pub fn fmt(&self, color: &Color) -> String {
-        let color = color.background();
-        let reset = Color::RESET;
-
+    fn pattern(&self) -> &'static str {
         match self {
-            Self::Full => format!("{color}      {reset} "),
-            Self::Left => format!("{color}  {reset}     "),
-            Self::Center => format!(" {color}  {reset}  "),
-            Self::Right => format!("    {color}  {reset} "),
-            Self::Sides => format!("{color}  {reset}  {color}  {reset} "),
-            Self::Empty => "     ".to_string(),
+            Self::Full => "###### ",
+            Self::Left => "##     ",
+            Self::Center => " ##  ",
+            Self::Right => "    ## ",
+            Self::Sides => "##  ## ",
+            Self::Empty => "     ",
         }
     }
+
+    pub fn width(&self) -> u16 {
+        self.pattern().len() as u16
+    }
+
+    pub fn cells(&self) -> Vec<bool> {
+        self.pattern().chars().map(|c| c == '#').collect()
+    }
+
+    pub fn fmt(&self, paint: &dyn Fn(u16) -> Color, reset: &str, fill: Option<char>) -> String {
+        fmt_cells(&self.cells(), paint, reset, fill)
+    }
+}
+
+pub fn fmt_cells(
+    cells: &[bool],
+    paint: &dyn Fn(u16) -> Color,
+    reset: &str,
+    fill: Option<char>,
+) -> String {
+    if let Some(fill) = fill {
+        return cells
+            .iter()
+            .map(|&on| if on { fill } else { ' ' })
+            .collect();
+    }
+
+    let mut string = String::new();
+    let mut current = None;
+
+    for (column, &on) in cells.iter().enumerate() {
+        if on {
+            let color = paint(column as u16);
+
+            if current.as_ref() != Some(&color) {
+                string.push_str(&color.background());
+                current = Some(color);
+            }
+        } else if current.take().is_some() {
+            string.push_str(reset);
+        }
+
+        string.push(' ');
+    }
+
+    if current.is_some() {
+        string.push_str(reset);
+    }
+
+    string
 }
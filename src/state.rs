@@ -7,12 +7,15 @@ use std::{
     time::Duration,
 };
 
-use clap::Parser;
 use crossterm::{
-    cursor::{Hide, MoveTo, Show},
+    cursor::{Hide, Show},
     event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
-    execute,
-    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
+    execute, queue,
+    style::ResetColor,
+    terminal::{
+        self, BeginSynchronizedUpdate, Clear, ClearType, EndSynchronizedUpdate,
+        EnterAlternateScreen, LeaveAlternateScreen,
+    },
 };
 
 #[cfg(unix)]
@@ -22,33 +25,89 @@ use crate::{
     cli::args::{Args, Mode, TimerArgs},
     clock::{
         counter::{Counter, CounterType},
-        mode::ClockMode,
+        mode::{ClockMode, ModeKind},
         time_zone::TimeZone,
-        Clock,
+        Area, Clock,
     },
+    command::Command,
     config::Config,
     error::Error,
+    help,
+    keys::{Action, KeysConfig},
+    pane::Split,
+    prompt::Prompt,
+    terminal::detect_background,
 };
 
 pub struct State {
-    clock: Clock,
+    clocks: Vec<Clock>,
+    configs: Vec<Config>,
+    saved: Vec<Config>,
+    prompt: Prompt,
+    help: bool,
+    split: Split,
+    panes: bool,
+    focus: usize,
 }
 
 impl State {
-    pub fn new() -> Result<Self, Error> {
-        let args = Args::parse();
-        let mut config = Config::parse()?;
+    pub fn new(args: Args) -> Result<Self, Error> {
+        let raw_config = Config::parse()?;
         let mode = args.mode.clone();
+        let panes = match mode {
+            None => raw_config.layout.panes.clone(),
+            Some(_) => Vec::new(),
+        };
+
+        let mut configs = Vec::new();
+
+        if panes.is_empty() {
+            let mut config = raw_config.clone();
+
+            args.overwrite(&mut config)?;
+
+            let clock_mode = Self::clock_mode(mode, &config)?;
+            configs.push((config, clock_mode));
+        } else {
+            for pane in &panes {
+                let mut config = pane.config(&raw_config, args.theme.as_deref())?;
 
-        args.overwrite(&mut config);
+                args.clone().overwrite_options(&mut config);
 
-        let clock_mode = Self::clock_mode(mode, &config)?;
-        let mut clock = Clock::new(config, clock_mode);
+                let clock_mode = pane.clock_mode(&config)?;
+                configs.push((config, clock_mode));
+            }
+        }
+
+        configs[0].0.general.color_depth.init();
+
+        if configs.iter().any(|(config, _)| config.uses_auto_color()) {
+            detect_background();
+        }
+
+        let (configs, clock_modes): (Vec<_>, Vec<_>) = configs.into_iter().unzip();
+
+        let mut state = Self {
+            clocks: configs
+                .iter()
+                .cloned()
+                .zip(clock_modes)
+                .map(|(config, clock_mode)| Clock::new(config, clock_mode))
+                .collect(),
+            saved: configs.clone(),
+            configs,
+            prompt: Prompt::default(),
+            help: false,
+            split: raw_config.layout.split,
+            panes: !panes.is_empty(),
+            focus: 0,
+        };
 
         let (width, height) = terminal::size().map_err(Error::Io)?;
-        clock.update_padding(width, height)?;
+        state.update_areas(width, height)?;
+        state.focus(0);
 
-        Ok(Self { clock })
+        Ok(state)
     }
 
     fn clock_mode(mode: Option<Mode>, config: &Config) -> Result<ClockMode, Error> {
@@ -60,7 +119,7 @@ impl State {
         } = match mode {
             Some(Mode::Clock) | None => {
                 return Ok(ClockMode::Time {
-                    time_zone: TimeZone::from_utc(config.date.utc),
+                    time_zone: TimeZone::from_config(&config.date),
                     date_format: config.date.fmt.clone(),
                 });
             }
@@ -68,6 +127,7 @@ impl State {
                 return Ok(ClockMode::Counter(Counter::new(CounterType::Stopwatch)))
             }
             Some(Mode::Timer(timer_args)) => timer_args,
+            Some(Mode::Themes(_)) => unreachable!("the themes command does not start a clock"),
         };
 
         let total_seconds = match (seconds, minutes, hours) {
@@ -100,6 +160,9 @@ impl State {
         terminal::enable_raw_mode()?;
         execute!(io::stdout(), EnterAlternateScreen, Hide)?;
 
+        let (width, height) = terminal::size()?;
+        self.refresh_display(width, height)?;
+
         let reload_config = Arc::new(AtomicBool::new(false));
 
         #[cfg(unix)]
@@ -112,45 +175,150 @@ impl State {
 
             self.render()?;
 
-            if !event::poll(self.clock.interval)? {
+            let tick = self
+                .clocks
+                .iter()
+                .map(Clock::tick)
+                .min()
+                .unwrap_or_default();
+
+            if !event::poll(tick)? {
                 continue;
             }
 
-            match event::read()? {
+            let event = event::read()?;
+
+            if matches!(event, Event::Key(_)) && self.prompt.dismiss() {
+                let (width, height) = terminal::size()?;
+                self.refresh_display(width, height)?;
+            }
+
+            match event {
+                Event::Key(KeyEvent {
+                    kind: KeyEventKind::Press,
+                    ..
+                }) if self.help => {
+                    self.help = false;
+
+                    let (width, height) = terminal::size()?;
+                    self.refresh_display(width, height)?;
+                }
+                Event::Key(key_event) if self.prompt.is_open() => {
+                    let quit = self.handle_prompt(key_event)?;
+
+                    if quit {
+                        return Ok(());
+                    }
+                }
                 Event::Key(key_event) => match key_event {
                     KeyEvent {
-                        code: KeyCode::Esc | KeyCode::Char('Q' | 'q'),
-                        modifiers: KeyModifiers::NONE,
+                        code: code @ (KeyCode::Esc | KeyCode::Enter | KeyCode::Backspace),
+                        kind: KeyEventKind::Press,
+                        ..
+                    } if self.timer().is_some_and(|timer| timer.is_entering()) => {
+                        let Some(timer) = self.timer() else {
+                            continue;
+                        };
+
+                        match code {
+                            KeyCode::Esc => timer.cancel_entry(),
+                            KeyCode::Enter => timer.confirm_entry(),
+                            _ => timer.erase_digit(),
+                        }
+
+                        let (width, height) = terminal::size()?;
+                        self.refresh_display(width, height)?;
+                    }
+                    key_event @ KeyEvent {
+                        kind: KeyEventKind::Press,
                         ..
+                    } if self.keys().action(&key_event).is_some() => {
+                        let Some(action) = self.keys().action(&key_event) else {
+                            continue;
+                        };
+
+                        match action {
+                            Action::Quit => return Ok(()),
+                            Action::Reload => reload_config.store(true, Ordering::Relaxed),
+                            _ => self.run_action(action)?,
+                        }
                     }
-                    | KeyEvent {
-                        code: KeyCode::Char('c'),
-                        modifiers: KeyModifiers::CONTROL,
+                    KeyEvent {
+                        code: KeyCode::Char(':'),
+                        kind: KeyEventKind::Press,
                         ..
-                    } => return Ok(()),
+                    } => self.prompt.open(),
                     KeyEvent {
-                        code: KeyCode::Char('r'),
-                        modifiers: KeyModifiers::CONTROL,
+                        code: KeyCode::Char('?'),
+                        kind: KeyEventKind::Press,
                         ..
-                    } => reload_config.store(true, Ordering::Relaxed),
+                    } => self.help = true,
                     KeyEvent {
-                        code: KeyCode::Char(character @ ('P' | 'p' | 'R' | 'r')),
+                        code: code @ (KeyCode::Tab | KeyCode::BackTab),
                         kind: KeyEventKind::Press,
-                        modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
                         ..
                     } => {
-                        let ClockMode::Counter(counter) = &mut self.clock.mode else {
+                        let len = self.clocks.len();
+                        let focus = match code {
+                            KeyCode::Tab => self.focus + 1,
+                            _ => self.focus + len - 1,
+                        };
+
+                        self.focus(focus % len);
+                    }
+                    KeyEvent {
+                        code: KeyCode::Char(character @ '0'..='9'),
+                        kind: KeyEventKind::Press,
+                        modifiers: KeyModifiers::NONE,
+                        ..
+                    } if self.timer().is_some() => {
+                        let Some(timer) = self.timer() else {
                             continue;
                         };
 
-                        match character {
-                            'P' | 'p' => counter.toggle_pause(),
-                            _ => counter.restart(),
-                        }
+                        timer.enter_digit(character.to_digit(10).unwrap_or_default());
 
                         let (width, height) = terminal::size()?;
                         self.refresh_display(width, height)?;
                     }
+                    KeyEvent {
+                        code: KeyCode::Char(character @ ('=' | '-' | '+' | '_')),
+                        kind: KeyEventKind::Press,
+                        modifiers,
+                        ..
+                    } if self.timer().is_some_and(|timer| !timer.is_entering()) => {
+                        let Some(timer) = self.timer() else {
+                            continue;
+                        };
+
+                        // `_` is Shift + `-` on US layouts, where the terminal does not report Shift.
+                        let minutes = match (character, modifiers.contains(KeyModifiers::SHIFT)) {
+                            ('_', _) => -10,
+                            ('-', true) => -10,
+                            ('-', false) => -1,
+                            (_, true) => 10,
+                            (_, false) => 1,
+                        };
+
+                        timer.adjust(minutes * 60);
+                    }
+                    KeyEvent {
+                        code: KeyCode::Char(character @ ('1' | '2' | '3')),
+                        kind: KeyEventKind::Press,
+                        modifiers: KeyModifiers::NONE | KeyModifiers::ALT,
+                        ..
+                    } => {
+                        let kind = match character {
+                            '1' => ModeKind::Clock,
+                            '2' => ModeKind::Stopwatch,
+                            _ => ModeKind::Timer,
+                        };
+
+                        if self.clocks[self.focus].switch_mode(kind) {
+                            let (width, height) = terminal::size()?;
+                            self.refresh_display(width, height)?;
+                        }
+                    }
                     _ => (),
                 },
                 Event::Resize(width, height) => self.refresh_display(width, height)?,
@@ -161,61 +329,392 @@ impl State {
     }
 
     pub fn exit() {
-        execute!(io::stdout(), LeaveAlternateScreen, Show).expect(
+        execute!(io::stdout(), ResetColor, LeaveAlternateScreen, Show).expect(
             "error: failed to leave alternate screen, you might have to restart your terminal",
         );
         terminal::disable_raw_mode()
             .expect("error: failed to disable raw mode, you might have to restart your terminal");
     }
 
+    fn handle_prompt(&mut self, key_event: KeyEvent) -> Result<bool, Error> {
+        if key_event.kind != KeyEventKind::Press {
+            return Ok(false);
+        }
+
+        match key_event.code {
+            KeyCode::Char('c') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.prompt.close();
+            }
+            KeyCode::Char(character) => self.prompt.insert(character),
+            KeyCode::Backspace => self.prompt.erase(),
+            KeyCode::Tab => self.prompt.complete(),
+            KeyCode::Esc => self.prompt.close(),
+            KeyCode::Enter => {
+                let input = self.prompt.submit().unwrap_or_default();
+
+                if self.execute(&input) {
+                    return Ok(true);
+                }
+            }
+            _ => (),
+        }
+
+        if !self.prompt.is_open() {
+            let (width, height) = terminal::size()?;
+            self.refresh_display(width, height)?;
+        }
+
+        Ok(false)
+    }
+
+    fn execute(&mut self, input: &str) -> bool {
+        let command = match input.parse::<Command>() {
+            Ok(Command::Quit) => return true,
+            Ok(Command::Write) => {
+                self.save();
+                return false;
+            }
+            Ok(command) => command,
+            Err(err) => {
+                self.prompt.show(Err(err));
+                return false;
+            }
+        };
+
+        let previous = self.configs[self.focus].clone();
+        let summary = command.apply(&mut self.clocks[self.focus], &mut self.configs[self.focus]);
+        let result = self
+            .reconfigure(previous)
+            .map(|()| summary.unwrap_or_else(|| input.trim().to_string()));
+
+        self.prompt.show(result);
+
+        false
+    }
+
+    /// Applies the configuration of the focused clock to it,
+    /// restoring `previous` if the clock cannot be laid out with the new settings.
+    fn reconfigure(&mut self, previous: Config) -> Result<(), String> {
+        let clock = &mut self.clocks[self.focus];
+        let config = &mut self.configs[self.focus];
+
+        clock.configure(config.clone());
+
+        clock.update_padding(clock.padding.area).map_err(|err| {
+            clock.configure(previous.clone());
+            *config = previous;
+
+            err.to_string()
+        })
+    }
+
+    /// Writes the settings of the focused clock that were changed since the last load or save.
+    fn save(&mut self) {
+        let config = &self.configs[self.focus];
+        let result = config
+            .save(&self.saved[self.focus], self.panes.then_some(self.focus))
+            .map(|path| format!("saved to {path}"))
+            .map_err(|err| err.to_string());
+
+        if result.is_ok() {
+            self.saved[self.focus] = config.clone();
+        }
+
+        self.prompt.show(result);
+    }
+
+    fn keys(&self) -> &KeysConfig {
+        &self.clocks[self.focus].keys
+    }
+
+    fn run_action(&mut self, action: Action) -> Result<(), Error> {
+        if let Some(toggle) = action.toggle() {
+            let previous = self.configs[self.focus].clone();
+
+            toggle.apply(&mut self.configs[self.focus], None);
+
+            if let Err(err) = self.reconfigure(previous) {
+                self.prompt.show(Err(err));
+            }
+        } else {
+            match (action, &mut self.clocks[self.focus].mode) {
+                (Action::Pause, ClockMode::Counter(counter)) => counter.toggle_pause(),
+                (Action::Restart, ClockMode::Counter(counter)) => counter.restart(),
+                (Action::Lap, ClockMode::Counter(counter)) => counter.lap(),
+                (Action::Save, _) => self.save(),
+                _ => return Ok(()),
+            }
+        }
+
+        let (width, height) = terminal::size()?;
+        self.refresh_display(width, height)
+    }
+
+    fn bindings(&self) -> Vec<(String, &'static str)> {
+        let keys = self.keys();
+        let mut bindings = Vec::new();
+        let mut bind = |binding: Option<(String, &'static str)>| bindings.extend(binding);
+        let key = |keys: &str, description| Some((keys.to_string(), description));
+        let action = |action: Action| Some((keys.label(action)?, action.description()));
+
+        match &self.clocks[self.focus].mode {
+            ClockMode::Counter(counter) if counter.is_entering() => {
+                bind(key("0-9", "Enter the duration"));
+                bind(key("Enter", "Start the timer"));
+                bind(key("Backspace", "Delete the last digit"));
+                bind(key("Esc", "Cancel the entry"));
+            }
+            ClockMode::Counter(counter) => {
+                bind(action(Action::Pause));
+                bind(action(Action::Restart));
+
+                if counter.is_stopwatch() {
+                    bind(action(Action::Lap));
+                } else {
+                    bind(key("0-9", "Enter a new duration"));
+                    bind(key("+ / -", "Add or subtract a minute"));
+                    bind(key("Shift with + / -", "Add or subtract ten minutes"));
+                }
+            }
+            ClockMode::Time { .. } => (),
+        }
+
+        bind(action(Action::ToggleSeconds));
+        bind(action(Action::Toggle12h));
+        bind(action(Action::ToggleBlink));
+        bind(action(Action::ToggleBold));
+        let modes = match &self.clocks[self.focus].mode {
+            ClockMode::Counter(counter) if !counter.is_stopwatch() => "Alt + 1 / 2 / 3",
+            _ => "1 / 2 / 3",
+        };
+
+        bind(key(modes, "Show the clock, stopwatch or timer"));
+
+        if self.clocks.len() > 1 {
+            bind(key("Tab / Shift + Tab", "Focus the next or previous pane"));
+        }
+
+        bind(key(":", "Open the command prompt"));
+        bind(key("?", "Show this help"));
+        bind(action(Action::Reload));
+        bind(action(Action::Save));
+        bind(action(Action::Quit));
+
+        bindings
+    }
+
+    fn timer(&mut self) -> Option<&mut Counter> {
+        match &mut self.clocks[self.focus].mode {
+            ClockMode::Counter(counter) if !counter.is_stopwatch() => Some(counter),
+            _ => None,
+        }
+    }
+
+    fn focus(&mut self, focus: usize) {
+        let single = self.clocks.len() == 1;
+
+        self.focus = focus;
+
+        for (i, clock) in self.clocks.iter_mut().enumerate() {
+            clock.focused = single || i == focus;
+        }
+    }
+
+    fn areas(&self, width: u16, height: u16) -> Vec<Area> {
+        let count = self.clocks.len() as u16;
+
+        (0..count)
+            .map(|i| {
+                let split = |len: u16| {
+                    let size = len / count;
+                    let start = i * size;
+                    let size = if i + 1 == count { len - start } else { size };
+
+                    (start, size)
+                };
+
+                match self.split {
+                    Split::Rows => {
+                        let (y, height) = split(height);
+
+                        Area {
+                            x: 0,
+                            y,
+                            width,
+                            height,
+                        }
+                    }
+                    Split::Columns => {
+                        let (x, width) = split(width);
+
+                        Area {
+                            x,
+                            y: 0,
+                            width,
+                            height,
+                        }
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn update_areas(&mut self, width: u16, height: u16) -> Result<(), Error> {
+        let areas = self.areas(width, height);
+        let count = self.clocks.len() as u16;
+        let panes = match self.split {
+            Split::Rows => (1, count),
+            Split::Columns => (count, 1),
+        };
+
+        for (clock, area) in self.clocks.iter_mut().zip(areas) {
+            clock.panes = panes;
+            clock.update_padding(area)?;
+        }
+
+        Ok(())
+    }
+
     fn refresh_display(&mut self, width: u16, height: u16) -> Result<(), Error> {
-        execute!(io::stdout(), Clear(ClearType::All))?;
-        self.clock.update_padding(width, height)
+        let mut stdout = io::stdout();
+
+        write!(stdout, "{}", self.clocks[0].reset())?;
+        execute!(stdout, Clear(ClearType::All))?;
+        self.update_areas(width, height)?;
+
+        if self.clocks.len() > 1 {
+            let mut buffered_writer = BufWriter::new(stdout.lock());
+
+            for clock in &self.clocks {
+                clock.clear_area(&mut buffered_writer)?;
+            }
+
+            buffered_writer.flush()?;
+        }
+
+        Ok(())
     }
 
     fn reload_config(&mut self) -> Result<(), Error> {
-        let clock = &mut self.clock;
-        let config = Config::parse()?;
+        let raw_config = Config::parse()?;
+        let panes = if self.panes {
+            raw_config.layout.panes.clone()
+        } else {
+            Vec::new()
+        };
 
-        clock.color = config.general.color;
-        clock.interval = Duration::from_millis(config.general.interval);
-        clock.blink = config.general.blink;
-        clock.bold = config.general.bold;
+        let mut configs = if panes.is_empty() {
+            let mut config = raw_config.clone();
 
-        clock.x_pos = config.position.x;
-        clock.y_pos = config.position.y;
+            config.apply_theme()?;
 
-        clock.use_12h = config.date.use_12h;
-        clock.hide_seconds = config.date.hide_seconds;
+            vec![config]
+        } else {
+            panes
+                .iter()
+                .map(|pane| pane.config(&raw_config, None))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        configs[0].general.color_depth.init();
+
+        if configs.iter().any(Config::uses_auto_color) {
+            detect_background();
+        }
+
+        self.configs.clone_from(&configs);
+        self.saved.clone_from(&configs);
+
+        if panes.is_empty() {
+            let mut clock = self.clocks.swap_remove(self.focus);
+
+            clock.configure(configs.remove(0));
+
+            self.clocks = vec![clock];
+            self.panes = false;
+            self.focus(0);
+        } else if panes.len() != self.clocks.len() {
+            self.clocks = panes
+                .iter()
+                .zip(configs)
+                .map(|(pane, config)| {
+                    let clock_mode = pane.clock_mode(&config)?;
+
+                    Ok(Clock::new(config, clock_mode))
+                })
+                .collect::<Result<_, Error>>()?;
+            self.split = raw_config.layout.split;
+            self.focus(0);
+        } else {
+            for ((clock, pane), config) in self.clocks.iter_mut().zip(&panes).zip(configs) {
+                clock.switch_mode(pane.mode);
+
+                clock.configure(config);
+            }
 
-        if let ClockMode::Time {
-            time_zone,
-            date_format,
-        } = &mut self.clock.mode
-        {
-            *time_zone = TimeZone::from_utc(config.date.utc);
-            *date_format = config.date.fmt;
+            self.split = raw_config.layout.split;
         }
 
         let (width, height) = terminal::size()?;
         self.refresh_display(width, height)
     }
 
-    fn render(&self) -> Result<(), Error> {
+    fn render(&mut self) -> Result<(), Error> {
         let (width, height) = terminal::size()?;
 
-        if self.clock.is_too_large(width, height) {
-            return Ok(());
+        let mut refresh = false;
+
+        for clock in &mut self.clocks {
+            refresh |= clock.update_style();
         }
 
-        let mut stdout = io::stdout();
+        if refresh {
+            self.refresh_display(width, height)?;
+        }
+
+        let mut buffered_writer = BufWriter::new(io::stdout().lock());
+
+        queue!(buffered_writer, BeginSynchronizedUpdate)?;
 
-        execute!(stdout, MoveTo(0, self.clock.padding.top))?;
+        for clock in &mut self.clocks {
+            clock.update_transitions();
+
+            let moved = clock.update_offset();
+            let area = clock.padding.area;
+
+            if clock.is_too_large(area.width, area.height) {
+                clock.fmt_fallback(&mut buffered_writer)?;
+                continue;
+            }
+
+            if moved {
+                clock.erase(&mut buffered_writer)?;
+                clock.update_padding(area)?;
+            }
+
+            clock.fmt(&mut buffered_writer)?;
+        }
+
+        let clock = &self.clocks[self.focus];
+
+        if self.help {
+            help::fmt(
+                &mut buffered_writer,
+                &self.bindings(),
+                (width, height),
+                &clock.reset(),
+                clock.fill.is_some(),
+            )?;
+        }
 
-        let lock = stdout.lock();
-        let mut buffered_writer = BufWriter::new(lock);
+        self.prompt.fmt(
+            &mut buffered_writer,
+            (width, height),
+            &clock.reset(),
+            clock.fill.is_some(),
+        )?;
 
-        self.clock.fmt(&mut buffered_writer)?;
+        queue!(buffered_writer, EndSynchronizedUpdate)?;
         buffered_writer.flush()?;
 
         Ok(())
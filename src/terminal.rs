@@ -0,0 +1,167 @@
+use std::env;
+#[cfg(unix)]
+use std::{
+    fs::OpenOptions,
+    io::{ErrorKind, Read, Write},
+    os::unix::fs::OpenOptionsExt,
+    thread,
+    time::{Duration, Instant},
+};
+
+#[cfg(unix)]
+use crossterm::terminal;
+use terminfo::{
+    capability::{MaxColors, TrueColor},
+    Database,
+};
+
+use crate::color::{Color, ColorDepth};
+
+#[cfg(unix)]
+const QUERY_TIMEOUT: Duration = Duration::from_millis(100);
+
+pub fn detect_background() {
+    let light = query_background()
+        .or_else(background_from_env)
+        .is_some_and(|color| {
+            let (r, g, b) = color.rgb();
+            let luminance = 0.2126 * f32::from(r) + 0.7152 * f32::from(g) + 0.0722 * f32::from(b);
+
+            luminance > 127.5
+        });
+
+    Color::set_light_background(light);
+}
+
+#[cfg(unix)]
+fn query_background() -> Option<Color> {
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open("/dev/tty")
+        .ok()?;
+
+    let raw_mode = terminal::is_raw_mode_enabled().ok()?;
+
+    if !raw_mode {
+        terminal::enable_raw_mode().ok()?;
+    }
+
+    let response = tty
+        .write_all(b"\x1B]11;?\x07")
+        .and_then(|()| tty.flush())
+        .ok()
+        .and_then(|()| read_response(&mut tty));
+
+    if !raw_mode {
+        terminal::disable_raw_mode().ok()?;
+    }
+
+    parse_osc_color(&response?)
+}
+
+#[cfg(not(unix))]
+fn query_background() -> Option<Color> {
+    None
+}
+
+#[cfg(unix)]
+fn read_response(tty: &mut impl Read) -> Option<String> {
+    let deadline = Instant::now() + QUERY_TIMEOUT;
+    let mut response = Vec::new();
+    let mut buffer = [0; 64];
+
+    while Instant::now() < deadline {
+        match tty.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(len) => {
+                response.extend_from_slice(&buffer[..len]);
+
+                if response.ends_with(b"\x07") || response.ends_with(b"\x1B\\") {
+                    return String::from_utf8(response).ok();
+                }
+            }
+            Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                thread::sleep(Duration::from_millis(5));
+            }
+            Err(_) => break,
+        }
+    }
+
+    None
+}
+
+fn parse_osc_color(response: &str) -> Option<Color> {
+    let (_, rgb) = response.split_once("rgb:")?;
+    let rgb = rgb.trim_end_matches(['\x07', '\x1B', '\\']);
+    let mut components = rgb.split('/').map(|hex| {
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        let max = 16u32.checked_pow(hex.len() as u32)? - 1;
+
+        Some((value * 255 / max) as u8)
+    });
+
+    Some(Color::Rgb {
+        r: components.next()??,
+        g: components.next()??,
+        b: components.next()??,
+    })
+}
+
+fn background_from_env() -> Option<Color> {
+    let colorfgbg = env::var("COLORFGBG").ok()?;
+    let background = colorfgbg.rsplit(';').next()?.parse().ok()?;
+
+    Some(Color::Indexed(background))
+}
+
+impl ColorDepth {
+    pub fn init(self) {
+        match self {
+            Self::Auto => Self::detect(),
+            _ => self,
+        }
+        .set();
+    }
+
+    fn detect() -> Self {
+        if env::var("COLORTERM")
+            .is_ok_and(|colorterm| colorterm == "truecolor" || colorterm == "24bit")
+        {
+            return Self::TrueColor;
+        }
+
+        let term = env::var("TERM").unwrap_or_default();
+
+        if term.ends_with("-direct") {
+            return Self::TrueColor;
+        }
+
+        if let Ok(database) = Database::from_env() {
+            let true_color = database
+                .get::<TrueColor>()
+                .is_some_and(|TrueColor(true_color)| true_color);
+
+            if true_color || database.raw("RGB").is_some() {
+                return Self::TrueColor;
+            }
+
+            if let Some(MaxColors(max_colors)) = database.get::<MaxColors>() {
+                return if max_colors >= 256 {
+                    Self::Ansi256
+                } else {
+                    Self::Ansi16
+                };
+            }
+        }
+
+        if term.contains("256color") {
+            Self::Ansi256
+        } else if term.is_empty() && cfg!(windows) {
+            Self::TrueColor
+        } else {
+            Self::Ansi16
+        }
+    }
+}
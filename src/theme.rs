@@ -0,0 +1,280 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    color::{Color, Palette},
+    config::{ColorsConfig, Config},
+    error::Error,
+};
+
+#[derive(Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub color: Option<Palette>,
+    pub bold: Option<bool>,
+    pub blink: Option<bool>,
+    pub colors: ColorsConfig,
+}
+
+impl Theme {
+    const BUILT_IN: [(&'static str, &'static str); 9] = [
+        (
+            "catppuccin-mocha",
+            r##"
+                color = "#cba6f7"
+                [colors]
+                colon = "#f5c2e7"
+                text = "#cdd6f4"
+                hint = "#6c7086"
+                background = "#1e1e2e"
+            "##,
+        ),
+        (
+            "dracula",
+            r##"
+                color = "#bd93f9"
+                bold = true
+                [colors]
+                colon = "#ff79c6"
+                text = "#f8f8f2"
+                hint = "#6272a4"
+                background = "#282a36"
+            "##,
+        ),
+        (
+            "gruvbox",
+            r##"
+                color = "#fabd2f"
+                bold = true
+                [colors]
+                colon = "#fe8019"
+                text = "#ebdbb2"
+                hint = "#928374"
+                background = "#282828"
+            "##,
+        ),
+        (
+            "matrix",
+            r##"
+                color = ["#00ff41", "#008f11"]
+                bold = true
+                blink = true
+                [colors]
+                text = "#00ff41"
+                hint = "#008f11"
+                background = "#0d0208"
+            "##,
+        ),
+        (
+            "monokai",
+            r##"
+                color = "#a6e22e"
+                [colors]
+                colon = "#f92672"
+                text = "#f8f8f2"
+                hint = "#75715e"
+                background = "#272822"
+            "##,
+        ),
+        (
+            "nord",
+            r##"
+                color = "#88c0d0"
+                [colors]
+                colon = "#81a1c1"
+                text = "#d8dee9"
+                hint = "#4c566a"
+                background = "#2e3440"
+            "##,
+        ),
+        (
+            "solarized-dark",
+            r##"
+                color = "#268bd2"
+                [colors]
+                colon = "#2aa198"
+                text = "#93a1a1"
+                hint = "#586e75"
+                background = "#002b36"
+            "##,
+        ),
+        (
+            "solarized-light",
+            r##"
+                color = "#268bd2"
+                [colors]
+                colon = "#d33682"
+                text = "#586e75"
+                hint = "#93a1a1"
+                background = "#fdf6e3"
+            "##,
+        ),
+        (
+            "tokyo-night",
+            r##"
+                color = ["#7aa2f7", "#bb9af7"]
+                [colors]
+                colon = "#7dcfff"
+                text = "#c0caf5"
+                hint = "#565f89"
+                background = "#1a1b26"
+            "##,
+        ),
+    ];
+    const PREVIEW_WIDTH: usize = 12;
+
+    /// Returns the `themes` directory next to the configuration file.
+    fn user_dir() -> Option<PathBuf> {
+        let config_path = Config::path(false).ok().flatten()?;
+
+        Some(Path::new(&config_path).parent()?.join("themes"))
+    }
+
+    fn parse(path: &str, theme_str: &str) -> Result<Self, Error> {
+        toml::from_str(theme_str).map_err(|err| Error::ParseToml {
+            path: path.to_string(),
+            err: err.to_string(),
+        })
+    }
+
+    fn read(path: PathBuf) -> Result<Self, Error> {
+        let path = path.display().to_string();
+        let theme_str = fs::read_to_string(&path).map_err(|err| Error::ReadFile {
+            path: path.clone(),
+            err: err.to_string(),
+        })?;
+
+        Self::parse(&path, &theme_str)
+    }
+
+    pub fn load(name: &str) -> Result<Self, Error> {
+        if let Some(path) = Self::user_dir().map(|dir| dir.join(format!("{name}.toml"))) {
+            if path.exists() {
+                return Self::read(path);
+            }
+        }
+
+        match Self::BUILT_IN
+            .iter()
+            .find(|(built_in, _)| *built_in == name)
+        {
+            Some((name, theme_str)) => Self::parse(name, theme_str),
+            None => Err(Error::ThemeNotFound(name.to_string())),
+        }
+    }
+
+    pub fn apply(self, config: &mut Config) {
+        if let Some(color) = self.color {
+            config.general.color = color;
+        }
+
+        if let Some(bold) = self.bold {
+            config.general.bold = bold;
+        }
+
+        if let Some(blink) = self.blink {
+            config.general.blink = blink;
+        }
+
+        let colors = &mut config.colors;
+
+        colors.digits = colors.digits.take().or(self.colors.digits);
+        colors.colon = colors.colon.take().or(self.colors.colon);
+        colors.text = colors.text.take().or(self.colors.text);
+        colors.hint = colors.hint.take().or(self.colors.hint);
+        colors.background = colors.background.take().or(self.colors.background);
+    }
+
+    pub fn overridden_by(self, other: Self) -> Self {
+        Self {
+            color: other.color.or(self.color),
+            bold: other.bold.or(self.bold),
+            blink: other.blink.or(self.blink),
+            colors: ColorsConfig {
+                digits: other.colors.digits.or(self.colors.digits),
+                colon: other.colors.colon.or(self.colors.colon),
+                text: other.colors.text.or(self.colors.text),
+                hint: other.colors.hint.or(self.colors.hint),
+                background: other.colors.background.or(self.colors.background),
+            },
+        }
+    }
+
+    pub fn list() -> Result<Vec<(String, Self)>, Error> {
+        let mut themes = Vec::new();
+
+        for (name, theme_str) in Self::BUILT_IN {
+            themes.push((name.to_string(), Self::parse(name, theme_str)?));
+        }
+
+        let entries = Self::user_dir().and_then(|dir| fs::read_dir(dir).ok());
+
+        for path in entries
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+        {
+            let Some(name) = path
+                .extension()
+                .filter(|extension| *extension == "toml")
+                .and(path.file_stem())
+            else {
+                continue;
+            };
+
+            let name = name.to_string_lossy().to_string();
+            let theme = Self::read(path)?;
+
+            match themes.iter_mut().find(|(built_in, _)| *built_in == name) {
+                Some(entry) => entry.1 = theme,
+                None => themes.push((name, theme)),
+            }
+        }
+
+        themes.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(themes)
+    }
+
+    pub fn preview(&self, name: &str) -> String {
+        let default = Palette::default();
+        let color = self.color.as_ref().unwrap_or(&default);
+        let digits = self.colors.digits.as_ref().unwrap_or(color);
+        let colon = self.colors.colon.as_ref().unwrap_or(color.first());
+        let text = self.colors.text.as_ref().unwrap_or(color.first());
+        let hint = self.colors.hint.as_ref().unwrap_or(color.first());
+
+        let reset = match &self.colors.background {
+            Some(background) => format!("{}{}", Color::RESET, background.background()),
+            None => Color::RESET.to_string(),
+        };
+        let bold = if self.bold.unwrap_or_default() {
+            Color::BOLD
+        } else {
+            ""
+        };
+
+        let mut preview = format!("{reset} ");
+
+        for i in 0..Self::PREVIEW_WIDTH {
+            let t = i as f32 / (Self::PREVIEW_WIDTH - 1) as f32;
+
+            preview.push_str(&format!("{} ", digits.at(t).background()));
+        }
+
+        preview.push_str(&format!(
+            "{reset} {} {reset} {bold}{}{name:<20}{reset}{}hint{reset} {}",
+            colon.background(),
+            text.foreground(),
+            hint.foreground(),
+            Color::RESET
+        ));
+
+        preview
+    }
+}
@@ -0,0 +1,52 @@
+use std::{str::FromStr, time::Duration};
+
+use serde::{de, Deserialize, Deserializer};
+
+use crate::{clock::counter::Counter, color::Palette, theme::Theme};
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Threshold(pub Duration);
+
+#[derive(Clone)]
+pub struct ThresholdStyle(pub Theme);
+
+impl<'de> Deserialize<'de> for Threshold {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string = String::deserialize(deserializer)?;
+
+        Counter::parse_duration(&string)
+            .map(Self)
+            .map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for ThresholdStyle {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum ThresholdStyleRepr {
+            String(String),
+            Theme(toml::Table),
+        }
+
+        Ok(Self(match ThresholdStyleRepr::deserialize(deserializer)? {
+            ThresholdStyleRepr::String(string) if string == "blink" => Theme {
+                blink: Some(true),
+                ..Default::default()
+            },
+            ThresholdStyleRepr::String(string) => Theme {
+                color: Some(Palette::from_str(&string).map_err(de::Error::custom)?),
+                ..Default::default()
+            },
+            ThresholdStyleRepr::Theme(theme) => {
+                Theme::deserialize(toml::Value::Table(theme)).map_err(de::Error::custom)?
+            }
+        }))
+    }
+}
@@ -0,0 +1,64 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Default, Deserialize, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Transition {
+    #[default]
+    None,
+    Slide,
+    Fade,
+    Morph,
+}
+
+impl Transition {
+    const HEIGHT: usize = 5;
+
+    pub fn cells(
+        &self,
+        old: &dyn Fn(usize) -> Vec<bool>,
+        new: &dyn Fn(usize) -> Vec<bool>,
+        row: usize,
+        progress: f32,
+    ) -> Vec<bool> {
+        match self {
+            Self::None => new(row),
+            Self::Fade if progress < 0.5 => old(row),
+            Self::Fade => new(row),
+            Self::Slide => {
+                let offset = (progress * (Self::HEIGHT + 1) as f32).round() as usize;
+
+                match row + offset.min(Self::HEIGHT + 1) {
+                    index if index < Self::HEIGHT => old(index),
+                    index if index == Self::HEIGHT => vec![false; new(row).len()],
+                    index => new(index - Self::HEIGHT - 1),
+                }
+            }
+            Self::Morph => old(row)
+                .into_iter()
+                .zip(new(row))
+                .enumerate()
+                .map(|(column, (old, new))| {
+                    if old == new || Self::threshold(column, row) >= progress {
+                        old
+                    } else {
+                        new
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    pub fn fade(&self, progress: f32) -> f32 {
+        match self {
+            Self::Fade => (1.0 - (progress * 2.0 - 1.0).abs()).clamp(0.0, 1.0),
+            _ => 0.0,
+        }
+    }
+
+    fn threshold(column: usize, row: usize) -> f32 {
+        let hash = ((column * 31 + row * 17 + 11) as u32).wrapping_mul(2_654_435_761);
+
+        (hash >> 24) as f32 / 256.0
+    }
+}